@@ -0,0 +1,1043 @@
+use core::panic;
+
+use regex::Regex;
+use syn::{FieldsNamed, FieldsUnnamed, Error, Expr, LitStr, Variant};
+use quote::{quote, ToTokens};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree, Ident, Span};
+
+use crate::{parse::{MessageAttribute, ExitCodeAttribute, FromAttribute, ParsedAttribute, Defaults, DefaultMessageAttribute, WrapMessageAttribute, DebugAttribute, FormatAttribute, FieldAttribute, StreamAttribute, BrokenPipeAttribute, OnSignalAttribute, AggregateAttribute, MergeAttribute, ExitCodeModeAttribute}, pull_up_results};
+
+pub fn generate_empty_debug_trait(name: &Ident) -> TokenStream2 {
+    let enum_name = format!("{}", name);
+    quote!(
+        impl std::fmt::Debug for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", #enum_name)
+            }
+        }
+    )
+}
+
+pub fn generate_empty_error_trait(name: &Ident) -> TokenStream2 {
+    quote!(
+        impl std::error::Error for #name {}
+    )
+}
+
+pub fn generate_empty_display_trait(name: &Ident) -> TokenStream2 {
+    let enum_name = format!("{}", name);
+    quote!(
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", #enum_name)
+            }
+        }
+    )
+}
+
+pub fn generate_empty_termination_trait(name: &Ident) -> TokenStream2 {
+    quote!(
+        impl #name {
+            /// Prints the termination output like returning it in a `Terminate` from `main` would, flushes stdout and stderr and exits the process with the exit code of the variant.
+            pub fn exit(self) -> ! {
+                match self {}
+            }
+        }
+
+        impl ::thistermination::TerminationError for #name {
+            fn exit_code(&self) -> u8 {
+                match *self {}
+            }
+
+            fn __terminate(self, _: &[&dyn std::fmt::Display], _: bool) -> u8 {
+                match self {}
+            }
+
+            fn __print(&self, _: &[&dyn std::fmt::Display]) {
+                match *self {}
+            }
+        }
+
+        impl std::process::Termination for #name {
+            fn report(self) -> std::process::ExitCode {
+                std::process::ExitCode::SUCCESS
+            }
+        }
+    )
+}
+
+/// The match arms that write the message of each variant, falling back to `Display` if a variant has no message.
+fn message_arms(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> Vec<TokenStream2> {
+    attributes.iter().map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        if attribute.aggregate.is_some() {
+            return message_impl_aggregate(name, attribute, defaults);
+        }
+        match &attribute.variant.fields {
+            syn::Fields::Named(f) => message_impl_named(name, variant_name, f, &attribute.fields, &attribute.message, defaults),
+            syn::Fields::Unnamed(f) => message_impl_unnamed(name, variant_name, f, &attribute.fields, &attribute.message, defaults),
+            syn::Fields::Unit => message_impl_unit(name, variant_name, &attribute.message, defaults),
+        }
+    }).collect()
+}
+
+pub fn generate_debug_trait(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> TokenStream2 {
+    let debug_impl = message_arms(name, attributes, defaults);
+    let fmt_impl = match defaults.debug.unwrap_or(DebugAttribute::Alternate) {
+        DebugAttribute::Message => quote! {
+            match self {
+                #(#debug_impl)*
+            }
+        },
+        DebugAttribute::Struct => {
+            let debug_struct_impl = attributes.iter().map(|attribute| debug_struct_impl(name, attribute, defaults, false));
+            quote! {
+                match self {
+                    #(#debug_struct_impl)*
+                }
+            }
+        }
+        DebugAttribute::Alternate => {
+            let debug_struct_impl = attributes.iter().map(|attribute| debug_struct_impl(name, attribute, defaults, true));
+            quote! {
+                if f.alternate() {
+                    use ::thistermination::__private::{WithDebug as _, WithoutDebug as _};
+                    match self {
+                        #(#debug_struct_impl)*
+                    }
+                }
+                match self {
+                    #(#debug_impl)*
+                }
+            }
+        }
+    };
+    quote! {
+        impl std::fmt::Debug for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #fmt_impl
+            }
+        }
+    }
+}
+
+pub fn generate_termination_trait(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults, print_message: bool) -> Result<TokenStream2, Error> {
+    let termination_impl = attributes.iter().map(|attribute| {
+    let variant_name = &attribute.variant.ident;
+    if let Some(signal) = &attribute.signal {
+        let pattern = variant_pattern(name, &attribute.variant);
+        return quote! { #pattern => ::thistermination::__private::signal_exit_code(::thistermination::__private::signals::#signal), };
+    }
+    if let Some(aggregate) = &attribute.aggregate {
+        return termination_impl_aggregate(name, attribute, aggregate, defaults);
+    }
+    match &attribute.variant.fields {
+            syn::Fields::Named(f) => termination_impl_named(name, variant_name, f, &attribute.exit_code, defaults),
+            syn::Fields::Unnamed(f) => termination_impl_unnamed(name, variant_name, f, &attribute.exit_code, defaults),
+            syn::Fields::Unit => termination_impl_unit(name, variant_name, &attribute.exit_code, defaults),
+        }
+    });
+    let signal_impl = generate_signal_raise(name, attributes);
+    let on_exit_impl = generate_on_exit(name, attributes, defaults);
+    let (broken_pipe_method_impl, broken_pipe_impl, broken_pipe_exit_code_impl) = match defaults.broken_pipe {
+        Some(BrokenPipeAttribute::Silent) => {
+            let broken_pipe_check = generate_broken_pipe_check(name, attributes);
+            let broken_pipe_method_impl = quote! {
+                /// Whether the enum or any of the fields of the variant is, or was caused by, a broken pipe.
+                #[doc(hidden)]
+                fn __thistermination_broken_pipe(&self) -> bool {
+                    #broken_pipe_check
+                }
+            };
+            (broken_pipe_method_impl, quote! { self.__thistermination_broken_pipe() }, quote! { if self.__thistermination_broken_pipe() { return 141; } })
+        }
+        None => (quote! {}, quote! { false }, quote! {}),
+    };
+    let report_impl = if print_message {
+        let help_impl = generate_optional_message(name, attributes, defaults, |attribute| attribute.help.as_ref(), defaults.help.as_ref(), false);
+        let detail_impl = if defaults.detail.is_none() && attributes.iter().all(|attribute| attribute.detail.is_none()) {
+            quote! { std::option::Option::None }
+        } else {
+            let detail_impl = generate_optional_message(name, attributes, defaults, |attribute| attribute.detail.as_ref(), defaults.detail.as_ref(), true);
+            quote! { if ::thistermination::is_verbose() { #detail_impl } else { std::option::Option::None } }
+        };
+        let variant_impl = attributes.iter().map(|attribute| {
+            let pattern = variant_pattern(name, &attribute.variant);
+            let variant_string = attribute.variant.ident.to_string();
+            quote! { #pattern => #variant_string, }
+        });
+        let code_impl = if attributes.iter().any(|attribute| attribute.code.is_some()) {
+            let code_impl = attributes.iter().map(|attribute| {
+                let pattern = variant_pattern(name, &attribute.variant);
+                match &attribute.code {
+                    Some(code) => quote! { #pattern => std::option::Option::Some(#code), },
+                    None => quote! { #pattern => std::option::Option::None, },
+                }
+            });
+            quote! { match self { #(#code_impl)* } }
+        } else {
+            quote! { std::option::Option::None }
+        };
+        let format = match defaults.format.unwrap_or(FormatAttribute::Text) {
+            FormatAttribute::Text => quote! { ::thistermination::__private::Format::Text },
+            FormatAttribute::Json => quote! { ::thistermination::__private::Format::Json },
+            FormatAttribute::Logfmt => quote! { ::thistermination::__private::Format::Logfmt },
+        };
+        let stream_impl = if defaults.stream.is_none() && attributes.iter().all(|attribute| attribute.stream.is_none()) {
+            stream_tokens(StreamAttribute::Stderr)
+        } else {
+            let stream_impl = attributes.iter().map(|attribute| {
+                let pattern = variant_pattern(name, &attribute.variant);
+                let stream = stream_tokens(attribute.stream.or(defaults.stream).unwrap_or(StreamAttribute::Stderr));
+                quote! { #pattern => #stream, }
+            });
+            quote! { match self { #(#stream_impl)* } }
+        };
+        let silent_impl = if attributes.iter().any(|attribute| attribute.silent) {
+            let silent_impl = attributes.iter().map(|attribute| {
+                let pattern = variant_pattern(name, &attribute.variant);
+                let silent = attribute.silent;
+                quote! { #pattern => #silent, }
+            });
+            quote! { match self { #(#silent_impl)* } }
+        } else {
+            quote! { false }
+        };
+        quote! {
+            #[allow(unused_imports)]
+            use ::thistermination::__private::{ViaError as _, ViaDebug as _};
+            let error = (&::thistermination::__private::ErrorCheck(self)).as_error();
+            ::thistermination::__private::Report {
+                variant: match self { #(#variant_impl)* },
+                exit_code: ::thistermination::TerminationError::exit_code(self),
+                message: self as &dyn ::thistermination::__private::Message,
+                context,
+                help: #help_impl,
+                detail: #detail_impl,
+                code: #code_impl,
+                error,
+                format: #format,
+                stream: #stream_impl,
+                silent: #silent_impl,
+            }
+        }
+    } else {
+        quote! {}
+    };
+    // The termination output uses the message, not the `Debug` impl, which prints a struct dump with `debug = "struct"`.
+    let message_trait_impl = if print_message {
+        let message_impl = message_arms(name, attributes, defaults);
+        quote! {
+            impl ::thistermination::__private::Message for #name {
+                fn fmt_message(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    match self {
+                        #(#message_impl)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let (report_method_impl, emit_impl, print_impl) = if print_message {
+        let report_method_impl = quote! {
+            /// Collects the termination output with `context` prepended to the message.
+            #[doc(hidden)]
+            fn __thistermination_report<'a>(&'a self, context: &'a [&'a dyn std::fmt::Display]) -> ::thistermination::__private::Report<'a> {
+                #report_impl
+            }
+        };
+        (report_method_impl, quote! { self.__thistermination_report(context).emit(); }, quote! { self.__thistermination_report(context).print(); })
+    } else {
+        (quote! {}, quote! {}, quote! { let _ = context; })
+    };
+    let signal_handlers_impl = generate_signal_handlers(name, attributes, defaults)?;
+    let panic_hook_impl = generate_panic_hook(name, attributes, defaults)?;
+    let bitflags_impl = generate_bitflags(name, attributes, defaults)?;
+    Ok(quote! {
+        impl #name {
+            /// Runs the hooks, prints the termination output with `context` prepended to the message if `print` is set and returns the exit code.
+            /// Shared by `report()`, which does not print to keep the output of returning the enum from `main` unchanged, `exit()`, `Terminate` and `ResultExt::context`.
+            #[doc(hidden)]
+            fn __thistermination_terminate(self, context: &[&dyn std::fmt::Display], print: bool) -> u8 {
+                let exit_code = ::thistermination::TerminationError::exit_code(&self);
+                let broken_pipe = #broken_pipe_impl;
+                // The event is emitted before the hooks, which may shut down the logger.
+                if !broken_pipe {
+                    #emit_impl
+                }
+                #on_exit_impl
+                // `exit_code` is already 141, nothing is printed and no signal is raised.
+                if broken_pipe {
+                    return exit_code;
+                }
+                if print {
+                    #print_impl
+                }
+                #signal_impl
+                exit_code
+            }
+
+            /// Emits the event and prints the termination output with `context` prepended to the message, without running hooks or raising signals.
+            #[doc(hidden)]
+            fn __thistermination_print(&self, context: &[&dyn std::fmt::Display]) {
+                if #broken_pipe_impl {
+                    return;
+                }
+                #emit_impl
+                #print_impl
+            }
+            #report_method_impl
+            #broken_pipe_method_impl
+
+            /// Prints the termination output like returning it in a `Terminate` from `main` would, flushes stdout and stderr and exits the process with the exit code of the variant.
+            pub fn exit(self) -> ! {
+                ::thistermination::__private::exit(self.__thistermination_terminate(&[], true))
+            }
+            #signal_handlers_impl
+            #panic_hook_impl
+            #bitflags_impl
+        }
+
+        #message_trait_impl
+
+        impl ::thistermination::TerminationError for #name {
+            fn exit_code(&self) -> u8 {
+                #broken_pipe_exit_code_impl
+                match *self {
+                    #(#termination_impl)*
+                }
+            }
+
+            fn __terminate(self, context: &[&dyn std::fmt::Display], print: bool) -> u8 {
+                self.__thistermination_terminate(context, print)
+            }
+
+            fn __print(&self, context: &[&dyn std::fmt::Display]) {
+                self.__thistermination_print(context)
+            }
+        }
+
+        impl std::process::Termination for #name {
+            fn report(self) -> std::process::ExitCode {
+                std::process::ExitCode::from(self.__thistermination_terminate(&[], false))
+            }
+        }
+    })
+}
+
+/// Checks that every variant declares its own bit for `exit_code_mode = bitflags` and generates `combine(...)`, which ORs the bits of several errors.
+fn generate_bitflags(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> Result<TokenStream2, Error> {
+    let Some(ExitCodeModeAttribute::Bitflags) = defaults.exit_code_mode else {
+        return Ok(quote! {});
+    };
+    if defaults.exit_code.is_some() {
+        return Err(Error::new_spanned(name, "exit_code on the enum cannot be combined with exit_code_mode = bitflags, every variant declares its own bit"));
+    }
+    let mut bits: Vec<(u8, &Ident)> = Vec::new();
+    for attribute in attributes {
+        let variant_name = &attribute.variant.ident;
+        if let Some(AggregateAttribute { ident, .. }) = &attribute.aggregate {
+            return Err(Error::new_spanned(ident, "aggregate cannot be combined with exit_code_mode = bitflags, use combine(...) instead"));
+        }
+        if let Some(signal) = &attribute.signal {
+            return Err(Error::new_spanned(signal, "signal(...) cannot be combined with exit_code_mode = bitflags"));
+        }
+        let Some(ExitCodeAttribute::Code(bit)) = attribute.exit_code else {
+            return Err(Error::new_spanned(&attribute.variant, "exit_code_mode = bitflags requires exit_code(<bit>) on every variant"));
+        };
+        if !bit.is_power_of_two() {
+            return Err(Error::new_spanned(&attribute.variant, format!("exit_code({}) is not a single bit, exit_code_mode = bitflags requires a power of two like 1, 2, 4 or 8", bit)));
+        }
+        if let Some((_, other)) = bits.iter().find(|(other_bit, _)| *other_bit == bit) {
+            return Err(Error::new_spanned(&attribute.variant, format!("exit_code({}) overlaps with {}, every bit can only be used by one variant", bit, other)));
+        }
+        bits.push((bit, variant_name));
+    }
+    Ok(quote! {
+        /// Combines the bits of `errors` into one exit code, `ExitCode::SUCCESS` if there are none.
+        pub fn combine<'a>(errors: impl std::iter::IntoIterator<Item = &'a Self>) -> std::process::ExitCode {
+            std::process::ExitCode::from(errors.into_iter().fold(0, |exit_code, error| exit_code | ::thistermination::TerminationError::exit_code(error)))
+        }
+    })
+}
+
+/// Generates `install_panic_hook()` for `panic = Variant`, which terminates through `Variant` when a thread panics.
+/// `Variant` is either a unit variant or has named fields `message`, `location` and `backtrace`, which are filled from the panic.
+fn generate_panic_hook(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> Result<TokenStream2, Error> {
+    let Some(variant) = &defaults.panic else {
+        return Ok(quote! {});
+    };
+    let Some(attribute) = attributes.iter().find(|attribute| attribute.variant.ident == *variant) else {
+        return Err(Error::new_spanned(variant, format!("{} has no variant {}", name, variant)));
+    };
+    let handler = match &attribute.variant.fields {
+        syn::Fields::Unit => quote! { |_| #name::#variant.exit() },
+        syn::Fields::Named(fields) => {
+            let field_names = pull_up_results(fields.named.iter().map(|field| {
+                let field_name = field.ident.as_ref().expect("named field without ident?");
+                if field_name == "message" || field_name == "location" || field_name == "backtrace" {
+                    Ok(field_name)
+                } else {
+                    Err(Error::new_spanned(field_name, "the panic variant can only have the fields message, location and backtrace"))
+                }
+            }))?;
+            quote! { |details| #name::#variant { #(#field_names: details.#field_names),* }.exit() }
+        }
+        syn::Fields::Unnamed(fields) => return Err(Error::new_spanned(fields, "the panic variant has to be a unit variant or have the named fields message, location and/or backtrace")),
+    };
+    Ok(quote! {
+        /// Replaces the panic hook with one that terminates the process through the variant given with `panic = ...`.
+        pub fn install_panic_hook() {
+            ::thistermination::__private::install_panic_hook(#handler)
+        }
+    })
+}
+
+/// Generates `install_signal_handlers()` for `on_signal(...) = Variant`, which terminates through `Variant` when one of the signals arrives.
+fn generate_signal_handlers(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> Result<TokenStream2, Error> {
+    if defaults.on_signal.is_empty() {
+        return Ok(quote! {});
+    }
+    for OnSignalAttribute { variant, .. } in &defaults.on_signal {
+        match attributes.iter().find(|attribute| attribute.variant.ident == *variant) {
+            Some(attribute) if matches!(attribute.variant.fields, syn::Fields::Unit) => {}
+            Some(_) => return Err(Error::new_spanned(variant, "on_signal(...) can only terminate through a unit variant")),
+            None => return Err(Error::new_spanned(variant, format!("{} has no variant {}", name, variant))),
+        }
+    }
+    let signals = defaults.on_signal.iter().flat_map(|attribute| &attribute.signals);
+    let signal_arms = defaults.on_signal.iter().map(|OnSignalAttribute { signals, variant }| {
+        quote! { signal if #(signal == ::thistermination::__private::signals::#signals)||* => #name::#variant, }
+    });
+    let signal_names = defaults.on_signal.iter().flat_map(|attribute| &attribute.signals).map(|signal| format!("`{}`", signal)).collect::<Vec<_>>().join(", ");
+    let doc = format!("Installs handlers for {} that terminate the process through the variant given with `on_signal(...)`. Can only be called once per process.", signal_names);
+    Ok(quote! {
+        #[doc = #doc]
+        pub fn install_signal_handlers() -> std::io::Result<()> {
+            ::thistermination::__private::install_signal_handlers(&[#(::thistermination::__private::signals::#signals),*], |signal| {
+                let variant = match signal {
+                    #(#signal_arms)*
+                    _ => return,
+                };
+                variant.exit()
+            })
+        }
+    })
+}
+
+/// Checks the enum and the fields of the variant for a broken pipe, `exit_code()` then returns 141 like a process killed by `SIGPIPE`.
+fn generate_broken_pipe_check(name: &Ident, attributes: &[ParsedAttribute]) -> TokenStream2 {
+    let field_checks = attributes.iter().map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        match &attribute.variant.fields {
+            syn::Fields::Named(fields) => {
+                let field_names: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+                quote! { #name::#variant_name { #(ref #field_names),* } => false #(|| ::thistermination::__private::is_broken_pipe((&::thistermination::__private::ErrorCheck(#field_names)).as_error()))*, }
+            }
+            syn::Fields::Unnamed(fields) => {
+                let field_names: Vec<_> = fields.unnamed.iter().enumerate().map(|(i, _)| syn::Ident::new(&format!("__{}", i), Span::call_site())).collect();
+                quote! { #name::#variant_name(#(ref #field_names),*) => false #(|| ::thistermination::__private::is_broken_pipe((&::thistermination::__private::ErrorCheck(#field_names)).as_error()))*, }
+            }
+            syn::Fields::Unit => quote! { #name::#variant_name => false, },
+        }
+    });
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::thistermination::__private::{ViaError as _, ViaDebug as _};
+            ::thistermination::__private::is_broken_pipe((&::thistermination::__private::ErrorCheck(self)).as_error()) || match *self {
+                #(#field_checks)*
+            }
+        }
+    }
+}
+
+/// Runs the `on_exit` hooks of the variant and then the ones of the enum, each in the order they were declared.
+fn generate_on_exit(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> TokenStream2 {
+    let enum_hooks = &defaults.on_exit;
+    if enum_hooks.is_empty() && attributes.iter().all(|attribute| attribute.on_exit.is_empty()) {
+        return quote! {};
+    }
+    let variant_hooks = attributes.iter().filter(|attribute| !attribute.on_exit.is_empty()).map(|attribute| {
+        let pattern = variant_pattern(name, &attribute.variant);
+        let hooks = &attribute.on_exit;
+        quote! { #pattern => { #(::thistermination::__private::run_exit_hook(|| #hooks(&self));)* } }
+    });
+    quote! {
+        #[allow(unreachable_patterns)]
+        match self {
+            #(#variant_hooks)*
+            _ => {}
+        }
+        #(::thistermination::__private::run_exit_hook(|| #enum_hooks(&self));)*
+    }
+}
+
+/// Re-raises the signal of variants with `signal(...)`, the exit code of those variants is only used if that does not terminate the process.
+fn generate_signal_raise(name: &Ident, attributes: &[ParsedAttribute]) -> TokenStream2 {
+    if attributes.iter().all(|attribute| attribute.signal.is_none()) {
+        return quote! {};
+    }
+    let signal_impl = attributes.iter().map(|attribute| {
+        let pattern = variant_pattern(name, &attribute.variant);
+        match &attribute.signal {
+            Some(signal) => quote! { #pattern => std::option::Option::Some(::thistermination::__private::signals::#signal), },
+            None => quote! { #pattern => std::option::Option::None, },
+        }
+    });
+    quote! {
+        if let std::option::Option::Some(signal) = match self { #(#signal_impl)* } {
+            ::thistermination::__private::raise_signal(signal);
+        }
+    }
+}
+
+fn stream_tokens(stream: StreamAttribute) -> TokenStream2 {
+    match stream {
+        StreamAttribute::Stderr => quote! { ::thistermination::__private::Stream::Stderr },
+        StreamAttribute::Stdout => quote! { ::thistermination::__private::Stream::Stdout },
+    }
+}
+
+fn variant_pattern(name: &Ident, variant: &Variant) -> TokenStream2 {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        syn::Fields::Named(_) => quote! { #name::#variant_name { .. } },
+        syn::Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
+        syn::Fields::Unit => quote! { #name::#variant_name },
+    }
+}
+
+/// Generates a `match *self` that formats the message picked by `message` for each variant, falling back to `default`.
+/// With `wrap` set, the message is wrapped in the `wrap_msg` template of the enum, like the message of the variant.
+fn generate_optional_message<'a>(name: &Ident, attributes: &'a [ParsedAttribute], defaults: &'a Defaults, message: impl Fn(&'a ParsedAttribute) -> Option<&'a MessageAttribute>, default: Option<&'a MessageAttribute>, wrap: bool) -> TokenStream2 {
+    if default.is_none() && attributes.iter().all(|attribute| message(attribute).is_none()) {
+        return quote! { std::option::Option::None };
+    }
+    let message_impl = attributes.iter().map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        let message = message(attribute).or(default);
+        let wrap = wrap.then_some((variant_name, defaults));
+        match &attribute.variant.fields {
+            syn::Fields::Named(fields) => {
+                let (field_bindings, shadows, sanitized) = named_field_bindings(fields, &attribute.fields, defaults.sanitize);
+                let message = optional_message(message, None, &sanitized, wrap);
+                quote! { #name::#variant_name { #(#field_bindings),* } => { #shadows #message } }
+            }
+            syn::Fields::Unnamed(fields) => {
+                let (field_bindings, shadows, sanitized) = unnamed_field_bindings(fields, &attribute.fields, defaults.sanitize);
+                let message = optional_message(message, Some("__"), &sanitized, wrap);
+                quote! { #name::#variant_name(#(#field_bindings),*) => { #shadows #message } }
+            }
+            syn::Fields::Unit => {
+                let message = optional_message(message, None, &[], wrap);
+                quote! { #name::#variant_name => #message, }
+            }
+        }
+    });
+    quote! {
+        match *self {
+            #(#message_impl)*
+        }
+    }
+}
+
+fn optional_message(message: Option<&MessageAttribute>, field_prefix: Option<&str>, sanitized: &[Ident], wrap: Option<(&Ident, &Defaults)>) -> TokenStream2 {
+    match message {
+        Some(MessageAttribute { format_string_lit, format_string_arguments }) => {
+            let format_string_lit = message_lit(format_string_lit, field_prefix, format_string_arguments, sanitized);
+            let format_string_arguments = message_arguments(format_string_arguments, sanitized);
+            let message = quote!(#format_string_lit, #(#format_string_arguments),*);
+            let message = match wrap {
+                Some((variant_name, defaults)) => wrap_message(variant_name, message, defaults),
+                None => message,
+            };
+            quote! { std::option::Option::Some(format!(#message)) }
+        }
+        None => quote! { std::option::Option::None },
+    }
+}
+
+pub fn generate_display_trait(name: &Ident, attributes: &[ParsedAttribute], defaults: &Defaults) -> Result<TokenStream2, Error> {
+    let display_impl = pull_up_results(attributes.iter().map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        if attribute.aggregate.is_some() {
+            return Ok(message_impl_aggregate(name, attribute, defaults));
+        }
+        if attribute.message.is_none() && defaults.message.is_none() && defaults.default_message.is_none() {
+            return Err(Error::new_spanned(&attribute.variant, "missing #[termination(msg(...))] attribute"));
+        }
+        Ok(match &attribute.variant.fields {
+            syn::Fields::Named(f) => message_impl_named(name, variant_name, f, &attribute.fields, &attribute.message, defaults),
+            syn::Fields::Unnamed(f) => message_impl_unnamed(name, variant_name, f, &attribute.fields, &attribute.message, defaults),
+            syn::Fields::Unit => message_impl_unit(name, variant_name, &attribute.message, defaults),
+        })
+    }))?;
+    Ok(quote! {
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#display_impl)*
+                }
+            }
+        }
+    })
+}
+
+pub fn generate_error_trait(name: &Ident, attributes: &[ParsedAttribute], from_attributes: &[FromAttribute]) -> TokenStream2 {
+    let transparent_impl = attributes.iter().filter(|attribute| attribute.transparent).map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        match &attribute.variant.fields {
+            syn::Fields::Named(fields) => {
+                let field_name = fields.named.first().expect("transparent with no fields is checked before").ident.as_ref().expect("field without ident?");
+                quote! { #name::#variant_name { ref #field_name } => std::error::Error::source(#field_name), }
+            }
+            syn::Fields::Unnamed(_) => quote! { #name::#variant_name(__0) => std::error::Error::source(__0), },
+            syn::Fields::Unit => panic!("transparent on unit variant should never happen"),
+        }
+    });
+    // Like thiserror, the #[from] field is the source, as long as it implements `std::error::Error`.
+    let from_impl = from_attributes.iter().filter(|from_attribute| from_attribute.from_type.is_some()).filter(|from_attribute| {
+        !attributes.iter().any(|attribute| attribute.transparent && attribute.variant.ident == from_attribute.variant.ident)
+    }).map(|from_attribute| {
+        let variant_name = &from_attribute.variant.ident;
+        match &from_attribute.variant.fields {
+            syn::Fields::Named(fields) => {
+                let field_name = fields.named.first().expect("from with no fields is checked before").ident.as_ref().expect("field without ident?");
+                quote! { #name::#variant_name { ref #field_name } => (&::thistermination::__private::ErrorCheck(#field_name)).as_error(), }
+            }
+            syn::Fields::Unnamed(_) => quote! { #name::#variant_name(__0) => (&::thistermination::__private::ErrorCheck(__0)).as_error(), },
+            syn::Fields::Unit => panic!("from on unit variant should never happen"),
+        }
+    });
+    let source_impl: Vec<TokenStream2> = transparent_impl.chain(from_impl).collect();
+    if source_impl.is_empty() {
+        return quote! {
+            impl std::error::Error for #name { }
+        };
+    }
+    quote! {
+        impl std::error::Error for #name {
+            fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
+                #[allow(unused_imports)]
+                use ::thistermination::__private::{ViaError as _, ViaDebug as _};
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#source_impl)*
+                    _ => std::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+pub fn generate_from_traits(name: &Ident, attributes: &[FromAttribute]) -> TokenStream2 {
+     let from_impl = attributes.iter().map(|attribute| {
+        let variant_name = &attribute.variant.ident;
+        if let Some(f_type) = &attribute.from_type {
+            let fn_impl = match &attribute.variant.fields {
+                syn::Fields::Named(fields) => {
+                    let field_name = fields.named.first().expect("from with no fields is checked before").ident.as_ref().expect("field without ident?");
+                    quote! { #name::#variant_name { #field_name: value } }
+                }
+                syn::Fields::Unnamed(_) => quote! { #name::#variant_name(value) },
+                syn::Fields::Unit => panic!("from on unit variant should never happen"),
+            };
+            quote! {
+                impl std::convert::From<#f_type> for #name {
+                    fn from(value: #f_type) -> Self {
+                        #fn_impl
+                    }
+                }
+            }
+        } else {
+            quote!{}
+        }
+    });
+    quote! {
+        #(#from_impl)*
+    }
+}
+
+fn termination_impl_named(name: &Ident, variant_name: &Ident, fields: &FieldsNamed, exit_code: &Option<ExitCodeAttribute>, defaults: &Defaults) -> TokenStream2 {
+    let field_names: Vec<_> = fields.named.iter().map(|field| field.ident.clone().expect("named field without ident?")).collect();
+    let exit_code = exit_code_impl(exit_code, defaults, &field_names);
+    quote! { #name::#variant_name { #(ref #field_names),* } => #exit_code, }
+}
+
+fn termination_impl_unnamed(name: &Ident, variant_name: &Ident, fields: &FieldsUnnamed, exit_code: &Option<ExitCodeAttribute>, defaults: &Defaults) -> TokenStream2 {
+    let field_names: Vec<_> = fields.unnamed.iter().enumerate().map(|(i, _)| {
+        syn::Ident::new(&format!("__{}", i), Span::call_site())
+    }).collect();
+    let exit_code = exit_code_impl(exit_code, defaults, &field_names);
+    quote! { #name::#variant_name( #(ref #field_names),* ) => #exit_code, }
+}
+
+fn termination_impl_unit(name: &Ident, variant_name: &Ident, exit_code: &Option<ExitCodeAttribute>, defaults: &Defaults) -> TokenStream2 {
+    let exit_code = exit_code_impl(exit_code, defaults, &[]);
+    quote! { #name::#variant_name => #exit_code, }
+}
+
+/// Merges the exit codes of the errors held by an `aggregate` variant, its `exit_code(...)` is used if there are none.
+fn termination_impl_aggregate(name: &Ident, attribute: &ParsedAttribute, aggregate: &AggregateAttribute, defaults: &Defaults) -> TokenStream2 {
+    let pattern = aggregate_pattern(name, &attribute.variant);
+    let fallback = exit_code_impl(&attribute.exit_code, defaults, &[]);
+    let merge = match aggregate.merge {
+        MergeAttribute::Max => quote! { ::thistermination::Merge::Max },
+        MergeAttribute::First => quote! { ::thistermination::Merge::First },
+        MergeAttribute::BitOr => quote! { ::thistermination::Merge::BitOr },
+    };
+    quote! { #pattern => ::thistermination::__private::aggregate_exit_code(__aggregate, #merge, #fallback), }
+}
+
+/// Binds the only field of an `aggregate` variant to `__aggregate`.
+fn aggregate_pattern(name: &Ident, variant: &Variant) -> TokenStream2 {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        syn::Fields::Named(fields) => {
+            let field_name = fields.named.first().expect("aggregate with no fields is checked before").ident.as_ref().expect("named field without ident?");
+            quote! { #name::#variant_name { #field_name: ref __aggregate } }
+        }
+        syn::Fields::Unnamed(_) => quote! { #name::#variant_name(ref __aggregate) },
+        syn::Fields::Unit => panic!("aggregate on unit variant should never happen"),
+    }
+}
+
+/// The `u8` exit code of a variant whose fields are bound to `field_names`.
+fn exit_code_impl(exit_code: &Option<ExitCodeAttribute>, defaults: &Defaults, field_names: &[Ident]) -> TokenStream2 {
+    let fallback = match defaults.exit_code {
+        Some(ExitCodeAttribute::Code(exit_code)) => exit_code,
+        _ => 1,
+    };
+    match exit_code {
+        Some(ExitCodeAttribute::Code(exit_code)) => quote! { #exit_code },
+        Some(ExitCodeAttribute::FromIoKind { overrides, .. }) => {
+            let overrides = overrides.iter().map(|(kind, exit_code)| quote! { (std::io::ErrorKind::#kind, #exit_code) });
+            quote! {
+                {
+                    #[allow(unused_imports)]
+                    use ::thistermination::__private::{ViaError as _, ViaDebug as _};
+                    ::thistermination::__private::io_kind_exit_code(&[#((&::thistermination::__private::ErrorCheck(#field_names)).as_error()),*], &[#(#overrides),*], #fallback)
+                }
+            }
+        }
+        Some(ExitCodeAttribute::FromStatus { field, .. }) => {
+            let binding = match field {
+                syn::Member::Named(name) => name.clone(),
+                syn::Member::Unnamed(index) => field_names[index.index as usize].clone(),
+            };
+            quote! { ::thistermination::__private::status_exit_code(#binding, #fallback) }
+        }
+        None => quote! { #fallback },
+    }
+}
+
+/// Binds the fields of a variant. Redacted fields are shadowed by `Redacted`, sanitized fields keep their value and get an alias
+/// wrapped in `Sanitized`, which `message_lit` puts in place of the field where a format string captures it.
+fn named_field_bindings(fields: &FieldsNamed, field_attributes: &[FieldAttribute], sanitize_all: bool) -> (Vec<TokenStream2>, TokenStream2, Vec<Ident>) {
+    let mut shadows = Vec::new();
+    let mut sanitized = Vec::new();
+    let field_bindings = fields.named.iter().zip(field_attributes).map(|(field, field_attribute)| {
+        let field_name = field.ident.as_ref().expect("named field without ident?");
+        if field_attribute.redact {
+            shadows.push(quote! { #[allow(unused_variables)] let #field_name = ::thistermination::__private::Redacted; });
+            quote! { #field_name: _ }
+        } else {
+            if sanitize_all || field_attribute.sanitize {
+                let alias = sanitized_alias(field_name);
+                shadows.push(quote! { #[allow(unused_variables)] let #alias = ::thistermination::__private::Sanitized(#field_name); });
+                sanitized.push(field_name.clone());
+            }
+            quote! { ref #field_name }
+        }
+    }).collect();
+    (field_bindings, quote! { #(#shadows)* }, sanitized)
+}
+
+fn unnamed_field_bindings(fields: &FieldsUnnamed, field_attributes: &[FieldAttribute], sanitize_all: bool) -> (Vec<TokenStream2>, TokenStream2, Vec<Ident>) {
+    let mut shadows = Vec::new();
+    let mut sanitized = Vec::new();
+    let field_bindings = fields.unnamed.iter().zip(field_attributes).enumerate().map(|(i, (_, field_attribute))| {
+        let field_name = syn::Ident::new(&format!("__{}", i), Span::call_site());
+        if field_attribute.redact {
+            shadows.push(quote! { #[allow(unused_variables)] let #field_name = ::thistermination::__private::Redacted; });
+            quote! { _ }
+        } else {
+            if sanitize_all || field_attribute.sanitize {
+                let alias = sanitized_alias(&field_name);
+                shadows.push(quote! { #[allow(unused_variables)] let #alias = ::thistermination::__private::Sanitized(#field_name); });
+                sanitized.push(field_name.clone());
+            }
+            quote! { ref #field_name }
+        }
+    }).collect();
+    (field_bindings, quote! { #(#shadows)* }, sanitized)
+}
+
+fn sanitized_alias(field_name: &Ident) -> Ident {
+    Ident::new(&format!("__thistermination_sanitized_{}", field_name.to_string().trim_start_matches("r#")), Span::call_site())
+}
+
+/// Rewrites the fields captured by a format string to the names they are bound to in the generated match arm: unnamed fields
+/// `{0}` become `{__0}` if `field_prefix` is set, and sanitized fields become their `Sanitized` alias. Fields used in the
+/// arguments, like `path.display()`, and captures that refer to a named argument are left alone.
+fn message_lit(format_string_lit: &LitStr, field_prefix: Option<&str>, arguments: &[Expr], sanitized: &[Ident]) -> LitStr {
+    let mut format_string = format_string_lit.value();
+    if let Some(prefix) = field_prefix {
+        format_string = get_formatted_string_with_fields(&format_string, prefix);
+    }
+    if sanitized.is_empty() {
+        return LitStr::new(&format_string, format_string_lit.span());
+    }
+    // Named arguments look like `name = value`, the tokens are checked because syn only exposes the fields of assignments with its "full" feature.
+    let named_arguments: Vec<String> = arguments.iter().filter_map(|argument| match argument {
+        Expr::Assign(assign) => {
+            let mut tokens = assign.to_token_stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(punct))) if punct.as_char() == '=' => Some(ident.to_string()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }).collect();
+    let regex = Regex::new(r#"\{\{|\}\}|\{([A-Za-z_][A-Za-z0-9_]*)(:[^}]*)?\}"#).expect("parsing regex");
+    let format_string = regex.replace_all(&format_string, |caps: &regex::Captures| {
+        let whole = caps.get(0).expect("the regex always matches something").as_str();
+        let Some(field) = caps.get(1).map(|m| m.as_str()) else {
+            return whole.to_string();
+        };
+        match sanitized.iter().find(|ident| *ident == field) {
+            Some(ident) if !named_arguments.iter().any(|argument| argument == field) => {
+                let spec = caps.get(2).map_or_else(|| "", |m| m.as_str());
+                format!("{{{}{}}}", sanitized_alias(ident), spec)
+            }
+            _ => whole.to_string(),
+        }
+    });
+    LitStr::new(&format_string, format_string_lit.span())
+}
+
+/// Wraps the format arguments that use a sanitized field, like `path.display()`, in `Sanitized`, so that their output is
+/// escaped as well. For named arguments only the value is wrapped.
+fn message_arguments(arguments: &[Expr], sanitized: &[Ident]) -> Vec<TokenStream2> {
+    arguments.iter().map(|argument| {
+        let tokens: Vec<TokenTree> = argument.to_token_stream().into_iter().collect();
+        let (name, value) = match (tokens.first(), tokens.get(1)) {
+            (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(punct))) if matches!(argument, Expr::Assign(_)) && punct.as_char() == '=' => tokens.split_at(2),
+            _ => tokens.split_at(0),
+        };
+        let value: TokenStream2 = value.iter().cloned().collect();
+        if uses_field(value.clone(), sanitized) {
+            quote! { #(#name)* ::thistermination::__private::Sanitized(&(#value)) }
+        } else {
+            argument.to_token_stream()
+        }
+    }).collect()
+}
+
+fn uses_field(tokens: TokenStream2, fields: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => fields.contains(&ident),
+        TokenTree::Group(group) => uses_field(group.stream(), fields),
+        _ => false,
+    })
+}
+
+/// With `fall_back_to_message` the dump only returns if every field implements `Debug`, otherwise the arm
+/// falls through to the message so that `debug = "alternate"` does not require `Debug` on the fields.
+fn debug_struct_impl(name: &Ident, attribute: &ParsedAttribute, defaults: &Defaults, fall_back_to_message: bool) -> TokenStream2 {
+    let variant_name = &attribute.variant.ident;
+    let variant_string = variant_name.to_string();
+    let (pattern, shadows, field_names, dump) = match &attribute.variant.fields {
+        syn::Fields::Named(fields) => {
+            let (field_bindings, shadows, sanitized) = named_field_bindings(fields, &attribute.fields, defaults.sanitize);
+            let field_strings: Vec<_> = fields.named.iter().map(|field| field.ident.as_ref().expect("named field without ident?").to_string()).collect();
+            let field_names = debug_field_names(fields.named.iter().map(|field| field.ident.clone().expect("named field without ident?")), &sanitized);
+            let field_values = debug_field_values(&field_names, fall_back_to_message);
+            (quote! { #name::#variant_name { #(#field_bindings),* } }, shadows, field_names, quote! { f.debug_struct(#variant_string)#(.field(#field_strings, #field_values))*.finish() })
+        }
+        syn::Fields::Unnamed(fields) => {
+            let (field_bindings, shadows, sanitized) = unnamed_field_bindings(fields, &attribute.fields, defaults.sanitize);
+            let field_names = debug_field_names((0..fields.unnamed.len()).map(|i| syn::Ident::new(&format!("__{}", i), Span::call_site())), &sanitized);
+            let field_values = debug_field_values(&field_names, fall_back_to_message);
+            (quote! { #name::#variant_name(#(#field_bindings),*) }, shadows, field_names, quote! { f.debug_tuple(#variant_string)#(.field(#field_values))*.finish() })
+        }
+        syn::Fields::Unit => (quote! { #name::#variant_name }, TokenStream2::new(), Vec::new(), quote! { f.write_str(#variant_string) }),
+    };
+    if !fall_back_to_message {
+        quote! { #pattern => { #shadows #dump } }
+    } else if field_names.is_empty() {
+        quote! { #pattern => return #dump, }
+    } else {
+        quote! {
+            #pattern => {
+                #shadows
+                if let (#(Some(#field_names),)*) = (#((&::thistermination::__private::DebugCheck(&#field_names)).as_debug(),)*) {
+                    return #dump;
+                }
+            }
+        }
+    }
+}
+
+/// The names the fields are dumped from, the `Sanitized` alias for sanitized fields.
+fn debug_field_names(field_names: impl Iterator<Item = Ident>, sanitized: &[Ident]) -> Vec<Ident> {
+    field_names.map(|field_name| if sanitized.contains(&field_name) { sanitized_alias(&field_name) } else { field_name }).collect()
+}
+
+fn debug_field_values(field_names: &[Ident], fall_back_to_message: bool) -> Vec<TokenStream2> {
+    field_names.iter().map(|field_name| if fall_back_to_message {
+        quote! { #field_name }
+    } else {
+        quote! { &#field_name }
+    }).collect()
+}
+
+/// The format arguments of `message`, wrapped in the `wrap_msg` template of the enum if it has one.
+fn wrap_message(variant_name: &Ident, message: TokenStream2, defaults: &Defaults) -> TokenStream2 {
+    if let Some(WrapMessageAttribute { template_lit, uses_variant }) = &defaults.wrap_message {
+        let variant_string = variant_name.to_string();
+        let variant_argument = uses_variant.then(|| quote! { variant = #variant_string, });
+        quote! { #template_lit, msg = format_args!(#message), #variant_argument }
+    } else {
+        message
+    }
+}
+
+fn write_message(variant_name: &Ident, message: TokenStream2, defaults: &Defaults) -> TokenStream2 {
+    let message = wrap_message(variant_name, message, defaults);
+    quote! { write!(f, #message) }
+}
+
+fn message_impl_named(name: &Ident, variant_name: &Ident, fields: &FieldsNamed, field_attributes: &[FieldAttribute], message: &Option<MessageAttribute>, defaults: &Defaults) -> TokenStream2 {
+    let (field_bindings, shadows, sanitized) = named_field_bindings(fields, field_attributes, defaults.sanitize);
+    if let Some(MessageAttribute { format_string_lit, format_string_arguments }) = message.as_ref().or(defaults.message.as_ref()) {
+        let format_string_lit = message_lit(format_string_lit, None, format_string_arguments, &sanitized);
+        let format_string_arguments = message_arguments(format_string_arguments, &sanitized);
+        let write_message = write_message(variant_name, quote!(#format_string_lit, #(#format_string_arguments),*), defaults);
+        quote! { #name::#variant_name { #(#field_bindings),* } => { #shadows #write_message } }
+    } else if let Some(default_message) = &defaults.default_message {
+        let field_references = fields.named.iter().map(|field| format!("{{{}}}", field.ident.as_ref().expect("named field without ident?")));
+        let default_lit = message_lit(&default_message_lit(default_message, variant_name, field_references), None, &[], &sanitized);
+        let write_message = write_message(variant_name, quote!(#default_lit), defaults);
+        quote! { #name::#variant_name { #(#field_bindings),* } => { #shadows #write_message } }
+    } else {
+        //This causes potential error to appear at the enum variant.
+        let self_ident = Ident::new("self", variant_name.span());
+        let write_message = write_message(variant_name, quote!("{}", #self_ident), defaults);
+        quote! { #name::#variant_name { #(#field_bindings),* } => { #shadows #write_message } }
+    }
+}
+
+/// Writes the message of an `aggregate` variant followed by one line per error, the field is still available under its own name for the message.
+fn message_impl_aggregate(name: &Ident, attribute: &ParsedAttribute, defaults: &Defaults) -> TokenStream2 {
+    let variant_name = &attribute.variant.ident;
+    let pattern = aggregate_pattern(name, &attribute.variant);
+    let (field_name, shadows, sanitized, prefix) = match &attribute.variant.fields {
+        syn::Fields::Named(fields) => {
+            let (_, shadows, sanitized) = named_field_bindings(fields, &attribute.fields, defaults.sanitize);
+            (fields.named.first().expect("aggregate with no fields is checked before").ident.clone().expect("named field without ident?"), shadows, sanitized, None)
+        }
+        syn::Fields::Unnamed(fields) => {
+            let (_, shadows, sanitized) = unnamed_field_bindings(fields, &attribute.fields, defaults.sanitize);
+            (Ident::new("__0", Span::call_site()), shadows, sanitized, Some("__"))
+        }
+        syn::Fields::Unit => panic!("aggregate on unit variant should never happen"),
+    };
+    let message = match &attribute.message {
+        Some(MessageAttribute { format_string_lit, format_string_arguments }) => {
+            let format_string = message_lit(format_string_lit, prefix, format_string_arguments, &sanitized).value();
+            let format_string_lit = LitStr::new(&format!("{}{{__thistermination_aggregate}}", format_string), format_string_lit.span());
+            let format_string_arguments = message_arguments(format_string_arguments, &sanitized);
+            quote!(#format_string_lit, #(#format_string_arguments,)* __thistermination_aggregate = ::thistermination::__private::Aggregate(__aggregate, false))
+        }
+        None => quote!("{}", ::thistermination::__private::Aggregate(__aggregate, true)),
+    };
+    let write_message = write_message(variant_name, message, defaults);
+    quote! { #pattern => { #[allow(unused_variables)] let #field_name = __aggregate; #shadows #write_message } }
+}
+
+fn get_formatted_string_with_fields(msg: &str, prefix: &str) -> String {
+    let regex = Regex::new(r#"\{(\d+)(:[^}]+)?\}"#).expect("parsing regex");
+    regex.replace_all(msg, |caps: &regex::Captures| {
+        let field = caps.get(1).expect("the regex always produces one capture group").as_str();
+        let args = caps.get(2).map_or_else(|| "", |m| m.as_str());
+        format!("{{{}{}{}}}",prefix, field, args)
+    }).to_string()
+}
+
+fn variant_name_to_sentence(variant_name: &Ident) -> String {
+    let name = variant_name.to_string();
+    let name = name.trim_start_matches("r#");
+    let chars: Vec<char> = name.chars().collect();
+    let mut sentence = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '_' {
+            if !sentence.is_empty() && !sentence.ends_with(' ') {
+                sentence.push(' ');
+            }
+            continue;
+        }
+        if c.is_uppercase() && i > 0 && !sentence.ends_with(' ') {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase) {
+                sentence.push(' ');
+            }
+        }
+        sentence.extend(c.to_lowercase());
+    }
+    sentence.trim_end().to_string()
+}
+
+fn default_message_lit(default_message: &DefaultMessageAttribute, variant_name: &Ident, field_references: impl Iterator<Item = String>) -> LitStr {
+    let message = match default_message {
+        DefaultMessageAttribute::Sentence => variant_name_to_sentence(variant_name),
+    };
+    let field_references: Vec<String> = field_references.collect();
+    if field_references.is_empty() {
+        LitStr::new(&message, variant_name.span())
+    } else {
+        LitStr::new(&format!("{}: {}", message, field_references.join(", ")), variant_name.span())
+    }
+}
+
+fn message_impl_unnamed(name: &Ident, variant_name: &Ident, fields: &FieldsUnnamed, field_attributes: &[FieldAttribute], message: &Option<MessageAttribute>, defaults: &Defaults) -> TokenStream2 {
+    let (field_bindings, shadows, sanitized) = unnamed_field_bindings(fields, field_attributes, defaults.sanitize);
+    if let Some(MessageAttribute { format_string_lit, format_string_arguments }) = message.as_ref().or(defaults.message.as_ref()) {
+        let updated_lit = message_lit(format_string_lit, Some("__"), format_string_arguments, &sanitized);
+        let format_string_arguments = message_arguments(format_string_arguments, &sanitized);
+        let write_message = write_message(variant_name, quote!(#updated_lit, #(#format_string_arguments),*), defaults);
+        quote! { #name::#variant_name(#(#field_bindings),*) => { #shadows #write_message } }
+    } else if let Some(default_message) = &defaults.default_message {
+        let field_references = (0..fields.unnamed.len()).map(|i| format!("{{__{}}}", i));
+        let default_lit = message_lit(&default_message_lit(default_message, variant_name, field_references), None, &[], &sanitized);
+        let write_message = write_message(variant_name, quote!(#default_lit), defaults);
+        quote! { #name::#variant_name(#(#field_bindings),*) => { #shadows #write_message } }
+    } else {
+        //This causes potential error to appear at the enum variant.
+        let self_ident = Ident::new("self", variant_name.span());
+        let write_message = write_message(variant_name, quote!("{}", #self_ident), defaults);
+        quote! { #name::#variant_name(#(#field_bindings),*) => { #shadows #write_message } }
+    }
+}
+
+fn message_impl_unit(name: &Ident, variant_name: &Ident, message: &Option<MessageAttribute>, defaults: &Defaults) -> TokenStream2 {
+    if let Some(MessageAttribute { format_string_lit, format_string_arguments, .. }) = message {
+        let write_message = write_message(variant_name, quote!(#format_string_lit, #(#format_string_arguments),*), defaults);
+        quote! { #name::#variant_name => #write_message, }
+    } else if let Some(MessageAttribute { format_string_lit, format_string_arguments }) = &defaults.message {
+        let write_message = write_message(variant_name, quote!(#format_string_lit, #(#format_string_arguments),*), defaults);
+        quote! { #name::#variant_name => #write_message, }
+    } else if let Some(default_message) = &defaults.default_message {
+        let default_lit = default_message_lit(default_message, variant_name, std::iter::empty());
+        let write_message = write_message(variant_name, quote!(#default_lit), defaults);
+        quote! { #name::#variant_name => #write_message, }
+    } else {
+        //This causes potential error to appear at the enum variant.
+        let self_ident = Ident::new("self", variant_name.span());
+        let write_message = write_message(variant_name, quote!("{}", #self_ident), defaults);
+        quote! { #name::#variant_name => #write_message, }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,772 @@
+use std::collections::HashSet;
+
+use quote::ToTokens;
+use regex::Regex;
+use syn::{Attribute, parenthesized, LitStr, LitInt, Token, Error, meta::ParseNestedMeta, Type, Variant, Expr, Fields, Ident, Member, Path, parse::ParseStream};
+
+use crate::pull_up_results;
+
+pub struct Defaults {
+    pub exit_code: Option<ExitCodeAttribute>,
+    pub message: Option<MessageAttribute>,
+    pub default_message: Option<DefaultMessageAttribute>,
+    pub wrap_message: Option<WrapMessageAttribute>,
+    pub debug: Option<DebugAttribute>,
+    pub help: Option<MessageAttribute>,
+    pub detail: Option<MessageAttribute>,
+    pub format: Option<FormatAttribute>,
+    pub stream: Option<StreamAttribute>,
+    pub broken_pipe: Option<BrokenPipeAttribute>,
+    pub on_signal: Vec<OnSignalAttribute>,
+    pub panic: Option<Ident>,
+    pub on_exit: Vec<Path>,
+    pub sanitize: bool,
+    pub exit_code_mode: Option<ExitCodeModeAttribute>,
+}
+
+impl From<TerminationAttributes> for Defaults {
+    fn from(value: TerminationAttributes) -> Self {
+        Self { exit_code: value.exit_code, message: value.message, default_message: value.default_message, wrap_message: value.wrap_message, debug: value.debug, help: value.help, detail: value.detail, format: value.format, stream: value.stream, broken_pipe: value.broken_pipe, on_signal: value.on_signal, panic: value.panic, on_exit: value.on_exit, sanitize: value.sanitize, exit_code_mode: value.exit_code_mode }
+    }
+}
+
+#[derive(Default)]
+pub struct TerminationAttributes {
+    pub exit_code: Option<ExitCodeAttribute>,
+    pub message: Option<MessageAttribute>,
+    pub default_message: Option<DefaultMessageAttribute>,
+    pub wrap_message: Option<WrapMessageAttribute>,
+    pub debug: Option<DebugAttribute>,
+    pub help: Option<MessageAttribute>,
+    pub detail: Option<MessageAttribute>,
+    pub format: Option<FormatAttribute>,
+    pub code: Option<LitStr>,
+    pub transparent: bool,
+    pub sanitize: bool,
+    pub silent: Option<Ident>,
+    pub stream: Option<StreamAttribute>,
+    pub broken_pipe: Option<BrokenPipeAttribute>,
+    pub signal: Option<Ident>,
+    pub on_signal: Vec<OnSignalAttribute>,
+    pub panic: Option<Ident>,
+    pub on_exit: Vec<Path>,
+    pub aggregate: Option<AggregateAttribute>,
+    pub exit_code_mode: Option<ExitCodeModeAttribute>,
+}
+
+pub struct ParsedAttribute {
+    pub variant: Variant,
+    pub exit_code: Option<ExitCodeAttribute>,
+    pub message: Option<MessageAttribute>,
+    pub help: Option<MessageAttribute>,
+    pub detail: Option<MessageAttribute>,
+    pub code: Option<LitStr>,
+    pub transparent: bool,
+    pub silent: bool,
+    pub stream: Option<StreamAttribute>,
+    pub signal: Option<Ident>,
+    pub on_exit: Vec<Path>,
+    pub aggregate: Option<AggregateAttribute>,
+    pub fields: Vec<FieldAttribute>,
+}
+
+#[derive(Default)]
+pub struct FieldAttribute {
+    pub redact: bool,
+    pub sanitize: bool,
+}
+
+pub struct MessageAttribute {
+    pub format_string_lit: LitStr,
+    pub format_string_arguments: Vec<Expr>,
+}
+
+pub enum ExitCodeAttribute {
+    Code(u8),
+    /// `exit_code(from_io_kind)`, optionally with `(Kind = code, ...)` entries that override the default table.
+    FromIoKind { ident: Ident, overrides: Vec<(Ident, u8)> },
+    /// `exit_code(from_status = field)`, forwards the `std::process::ExitStatus` stored in `field`.
+    FromStatus { ident: Ident, field: Member },
+}
+
+pub enum DefaultMessageAttribute {
+    Sentence,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DebugAttribute {
+    Message,
+    Struct,
+    Alternate,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FormatAttribute {
+    Text,
+    Json,
+    Logfmt,
+}
+
+/// `exit_code_mode = bitflags`, every variant declares a bit and the bits of several errors can be combined.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExitCodeModeAttribute {
+    Bitflags,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StreamAttribute {
+    Stderr,
+    Stdout,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BrokenPipeAttribute {
+    Silent,
+}
+
+/// `aggregate` or `aggregate = "first"` on a variant whose only field holds the inner errors.
+pub struct AggregateAttribute {
+    pub ident: Ident,
+    pub merge: MergeAttribute,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MergeAttribute {
+    Max,
+    First,
+    BitOr,
+}
+
+/// `on_signal(SIGINT, SIGTERM) = Variant`
+pub struct OnSignalAttribute {
+    pub signals: Vec<Ident>,
+    pub variant: Ident,
+}
+
+pub struct WrapMessageAttribute {
+    pub template_lit: LitStr,
+    pub uses_variant: bool,
+}
+
+pub struct FromAttribute {
+    pub variant: Variant,
+    pub from_type: Option<Type>,
+}
+
+pub fn parse_helper_attributes<'a>(variants: impl Iterator<Item = &'a Variant>, error_alias: bool) -> Result<Vec<ParsedAttribute>, Error> {
+    pull_up_results(variants.map(|variant| {
+        let TerminationAttributes { exit_code, mut message, default_message, wrap_message, debug, help, detail, format, code, transparent, sanitize, silent, stream, broken_pipe, signal, on_signal, panic, on_exit, aggregate, exit_code_mode } = parse_attributes(&variant.attrs, error_alias)?;
+        if default_message.is_some() {
+            return Err(Error::new_spanned(variant, "default_msg can only be used on the enum itself"));
+        }
+        if wrap_message.is_some() {
+            return Err(Error::new_spanned(variant, "wrap_msg can only be used on the enum itself"));
+        }
+        if debug.is_some() {
+            return Err(Error::new_spanned(variant, "debug can only be used on the enum itself"));
+        }
+        if format.is_some() {
+            return Err(Error::new_spanned(variant, "format can only be used on the enum itself"));
+        }
+        if exit_code_mode.is_some() {
+            return Err(Error::new_spanned(variant, "exit_code_mode can only be used on the enum itself"));
+        }
+        if let Some(ExitCodeAttribute::FromIoKind { ident, .. }) = &exit_code {
+            if variant.fields.is_empty() {
+                return Err(Error::new_spanned(ident, "exit_code(from_io_kind) requires a field that holds the std::io::Error"));
+            }
+        }
+        if let Some(ExitCodeAttribute::FromStatus { field, .. }) = &exit_code {
+            let exists = match (&variant.fields, field) {
+                (Fields::Named(fields), Member::Named(name)) => fields.named.iter().any(|field| field.ident.as_ref() == Some(name)),
+                (Fields::Unnamed(fields), Member::Unnamed(index)) => (index.index as usize) < fields.unnamed.len(),
+                _ => false,
+            };
+            if !exists {
+                return Err(Error::new_spanned(field, "exit_code(from_status = ...) has to name a field of this variant"));
+            }
+        }
+        if let (Some(signal), Some(_)) = (&signal, &exit_code) {
+            return Err(Error::new_spanned(signal, "signal(...) cannot be combined with exit_code(...), the exit code is 128 + the signal number"));
+        }
+        if panic.is_some() {
+            return Err(Error::new_spanned(variant, "panic can only be used on the enum itself"));
+        }
+        if !on_signal.is_empty() {
+            return Err(Error::new_spanned(variant, "on_signal can only be used on the enum itself"));
+        }
+        if broken_pipe.is_some() {
+            return Err(Error::new_spanned(variant, "broken_pipe can only be used on the enum itself"));
+        }
+        if sanitize {
+            return Err(Error::new_spanned(variant, "sanitize can only be used on the enum itself or on fields"));
+        }
+        if silent.is_some() && stream.is_some() {
+            return Err(Error::new_spanned(variant, "silent cannot be combined with stream = ..., a silent variant does not print anything"));
+        }
+        if transparent {
+            message = Some(transparent_message(variant)?);
+        }
+        let fields = pull_up_results(variant.fields.iter().map(|field| parse_field_attributes(&field.attrs)))?;
+        if let Some(AggregateAttribute { ident, .. }) = &aggregate {
+            if variant.fields.len() != 1 {
+                return Err(Error::new_spanned(ident, "aggregate requires exactly one field that holds the errors"));
+            }
+            if transparent {
+                return Err(Error::new_spanned(ident, "aggregate cannot be combined with #[error(transparent)]"));
+            }
+            if signal.is_some() {
+                return Err(Error::new_spanned(ident, "aggregate cannot be combined with signal(...), the exit code is derived from the errors"));
+            }
+            if matches!(exit_code, Some(ExitCodeAttribute::FromIoKind { .. } | ExitCodeAttribute::FromStatus { .. })) {
+                return Err(Error::new_spanned(ident, "aggregate can only be combined with exit_code(<code>), which is used if there are no errors"));
+            }
+            if fields.iter().any(|field| field.redact) {
+                return Err(Error::new_spanned(ident, "the errors of an aggregate variant cannot be redacted"));
+            }
+        }
+        Ok(ParsedAttribute { variant: variant.clone(), exit_code, message, help, detail, code, transparent, silent: silent.is_some(), stream, signal, on_exit, aggregate, fields })
+    }))
+}
+
+fn parse_field_attributes(attributes: &[Attribute]) -> Result<FieldAttribute, Error> {
+    let mut parsed = FieldAttribute::default();
+    for attribute in attributes {
+        if !attribute.path().is_ident("termination") {
+            continue;
+        }
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("redact") {
+                if parsed.redact {
+                    return Err(meta.error("Only one redact per field is allowed."));
+                }
+                parsed.redact = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("sanitize") {
+                if parsed.sanitize {
+                    return Err(meta.error("Only one sanitize per field is allowed."));
+                }
+                parsed.sanitize = true;
+                return Ok(());
+            }
+            Err(meta.error("unrecognized field attribute, expected redact or sanitize"))
+        })?;
+    }
+    Ok(parsed)
+}
+
+pub fn parse_enum_attributes(attributes: &[Attribute], error_alias: bool) -> Result<Defaults, Error> {
+    let parsed = parse_attributes(attributes, error_alias)?;
+    if parsed.transparent {
+        let attribute = attributes.iter().find(|attribute| attribute.path().is_ident("error")).expect("transparent is only set by #[error(transparent)]");
+        return Err(Error::new_spanned(attribute, "#[error(transparent)] can only be used on enum variants"));
+    }
+    if let Some(code) = &parsed.code {
+        return Err(Error::new_spanned(code, "code can only be used on enum variants"));
+    }
+    if let Some(ExitCodeAttribute::FromIoKind { ident, .. } | ExitCodeAttribute::FromStatus { ident, .. }) = &parsed.exit_code {
+        return Err(Error::new_spanned(ident, format!("exit_code({}) can only be used on enum variants", ident)));
+    }
+    if let Some(signal) = &parsed.signal {
+        return Err(Error::new_spanned(signal, "signal can only be used on enum variants"));
+    }
+    if let Some(silent) = &parsed.silent {
+        return Err(Error::new_spanned(silent, "silent can only be used on enum variants"));
+    }
+    if let Some(AggregateAttribute { ident, .. }) = &parsed.aggregate {
+        return Err(Error::new_spanned(ident, "aggregate can only be used on enum variants"));
+    }
+    Ok(parsed.into())
+}
+
+fn transparent_message(variant: &Variant) -> Result<MessageAttribute, Error> {
+    if variant.fields.len() != 1 {
+        return Err(Error::new_spanned(variant, "#[error(transparent)] requires exactly one field"));
+    }
+    let format_string = match &variant.fields {
+        Fields::Named(fields) => format!("{{{}}}", fields.named.first().expect("checked before").ident.as_ref().expect("named field without ident?")),
+        _ => "{0}".to_string(),
+    };
+    Ok(MessageAttribute { format_string_lit: LitStr::new(&format_string, variant.ident.span()), format_string_arguments: Vec::new() })
+}
+
+pub fn parse_from_attribute<'a>(variants: impl Iterator<Item = &'a Variant>) -> Result<Vec<FromAttribute>, Error> {
+    pull_up_results(variants.map(|variant| {
+        let mut field_type = None;
+        if variant.fields.is_empty() {
+            return Ok(FromAttribute { variant: variant.clone(), from_type: None});
+        }
+        for field in variant.fields.iter() {
+            for attribute in &field.attrs {
+                if let Some(ident) = attribute.path().get_ident() {
+                    if *ident == "from" {
+                        // ugly but easy
+                        if attribute.to_token_stream().to_string() != "#[from]" {
+                            return Err(Error::new_spanned(attribute, "expected #[from]"))
+                        }
+                        if field_type.is_none() {
+                            field_type = Some(field.ty.clone());
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        if field_type.is_some() && variant.fields.len() > 1 {
+            return Err(Error::new_spanned(&variant.fields, "Only one field is allowed when using #[from]."))
+        }
+        Ok(FromAttribute { variant: variant.clone(), from_type: field_type})
+    }))
+}
+
+pub fn check_for_unique_types(attributes: &[FromAttribute]) -> Result<(), Error> {
+    let mut types = HashSet::new();
+    for attribute in attributes {
+        if let Some(from_type) = &attribute.from_type {
+            let type_string = from_type.to_token_stream().to_string();
+            if !types.contains(&type_string) {
+                types.insert(type_string);
+            } else {
+                return Err(Error::new_spanned(&attribute.variant, "cannot use #[from] because another variant has the same type annotated with #[from]."));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_exit_code(meta: &ParseNestedMeta<'_>) -> Result<ExitCodeAttribute, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    if content.peek(Ident) {
+        let ident: Ident = content.parse()?;
+        if ident == "from_status" {
+            content.parse::<Token![=]>()?;
+            let field: Member = content.parse()?;
+            return Ok(ExitCodeAttribute::FromStatus { ident, field });
+        }
+        if ident != "from_io_kind" {
+            return Err(Error::new_spanned(ident, "expected an exit code, from_io_kind or from_status = field"));
+        }
+        let mut overrides = Vec::new();
+        if !content.is_empty() {
+            let overrides_content;
+            parenthesized!(overrides_content in content);
+            while !overrides_content.is_empty() {
+                let kind: Ident = overrides_content.parse()?;
+                overrides_content.parse::<Token![=]>()?;
+                let lit: LitInt = overrides_content.parse()?;
+                if overrides.iter().any(|(existing, _)| *existing == kind) {
+                    return Err(Error::new_spanned(kind, "Only one exit code per io::ErrorKind is allowed."));
+                }
+                overrides.push((kind, lit.base10_parse()?));
+                if !overrides_content.is_empty() {
+                    overrides_content.parse::<Token![,]>()?;
+                }
+            }
+        }
+        return Ok(ExitCodeAttribute::FromIoKind { ident, overrides });
+    }
+    let lit: LitInt = content.parse()?;
+    Ok(ExitCodeAttribute::Code(lit.base10_parse()?))
+}
+
+fn parse_message(meta: &ParseNestedMeta<'_>) -> Result<MessageAttribute, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    parse_message_content(&content)
+}
+
+fn parse_message_content(content: ParseStream<'_>) -> Result<MessageAttribute, Error> {
+    let lit: LitStr = content.parse()?;
+    let mut args = Vec::new();
+    while !content.is_empty() {
+        content.parse::<Token![,]>()?;
+        let arg: Expr = content.parse()?;
+        args.push(arg);
+    }
+    Ok(MessageAttribute { format_string_lit: lit, format_string_arguments: args })
+}
+
+fn parse_default_message(meta: &ParseNestedMeta<'_>) -> Result<DefaultMessageAttribute, Error> {
+    let lit: LitStr = meta.value()?.parse()?;
+    match lit.value().as_str() {
+        "sentence" => Ok(DefaultMessageAttribute::Sentence),
+        _ => Err(Error::new_spanned(lit, "unknown default_msg style, expected \"sentence\"")),
+    }
+}
+
+fn parse_broken_pipe(meta: &ParseNestedMeta<'_>) -> Result<BrokenPipeAttribute, Error> {
+    let lit: LitStr = meta.value()?.parse()?;
+    match lit.value().as_str() {
+        "silent" => Ok(BrokenPipeAttribute::Silent),
+        _ => Err(Error::new_spanned(lit, "unknown broken_pipe behavior, expected \"silent\"")),
+    }
+}
+
+fn parse_aggregate(meta: &ParseNestedMeta<'_>, ident: &Ident) -> Result<AggregateAttribute, Error> {
+    if !meta.input.peek(Token![=]) {
+        return Ok(AggregateAttribute { ident: ident.clone(), merge: MergeAttribute::Max });
+    }
+    let lit: LitStr = meta.value()?.parse()?;
+    let merge = match lit.value().as_str() {
+        "max" => MergeAttribute::Max,
+        "first" => MergeAttribute::First,
+        "bitor" => MergeAttribute::BitOr,
+        _ => return Err(Error::new_spanned(lit, "unknown aggregate merge, expected \"max\", \"first\" or \"bitor\"")),
+    };
+    Ok(AggregateAttribute { ident: ident.clone(), merge })
+}
+
+fn parse_debug(meta: &ParseNestedMeta<'_>) -> Result<DebugAttribute, Error> {
+    let lit: LitStr = meta.value()?.parse()?;
+    match lit.value().as_str() {
+        "message" => Ok(DebugAttribute::Message),
+        "struct" => Ok(DebugAttribute::Struct),
+        "alternate" => Ok(DebugAttribute::Alternate),
+        _ => Err(Error::new_spanned(lit, "unknown debug style, expected \"message\", \"struct\" or \"alternate\"")),
+    }
+}
+
+fn parse_format(meta: &ParseNestedMeta<'_>) -> Result<FormatAttribute, Error> {
+    let lit: LitStr = meta.value()?.parse()?;
+    match lit.value().as_str() {
+        "text" => Ok(FormatAttribute::Text),
+        "json" => Ok(FormatAttribute::Json),
+        "logfmt" => Ok(FormatAttribute::Logfmt),
+        _ => Err(Error::new_spanned(lit, "unknown format, expected \"text\", \"json\" or \"logfmt\"")),
+    }
+}
+
+fn parse_exit_code_mode(meta: &ParseNestedMeta<'_>) -> Result<ExitCodeModeAttribute, Error> {
+    let ident: Ident = meta.value()?.parse()?;
+    if ident == "bitflags" {
+        Ok(ExitCodeModeAttribute::Bitflags)
+    } else {
+        Err(Error::new_spanned(ident, "unknown exit_code_mode, expected bitflags"))
+    }
+}
+
+fn parse_stream(meta: &ParseNestedMeta<'_>) -> Result<StreamAttribute, Error> {
+    let ident: Ident = meta.value()?.parse()?;
+    if ident == "stderr" {
+        Ok(StreamAttribute::Stderr)
+    } else if ident == "stdout" {
+        Ok(StreamAttribute::Stdout)
+    } else {
+        Err(Error::new_spanned(ident, "unknown stream, expected stderr or stdout"))
+    }
+}
+
+/// The signals exported by `thistermination::__private::signals`.
+pub const SIGNALS: [&str; 9] = ["SIGHUP", "SIGINT", "SIGQUIT", "SIGABRT", "SIGPIPE", "SIGALRM", "SIGTERM", "SIGUSR1", "SIGUSR2"];
+
+pub fn parse_signal_name(input: ParseStream<'_>) -> Result<Ident, Error> {
+    let ident: Ident = input.parse()?;
+    if !SIGNALS.iter().any(|signal| ident == signal) {
+        return Err(Error::new_spanned(&ident, format!("unknown signal, expected one of {}", SIGNALS.join(", "))));
+    }
+    Ok(ident)
+}
+
+fn parse_signal(meta: &ParseNestedMeta<'_>) -> Result<Ident, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    parse_signal_name(&content)
+}
+
+fn parse_on_signal(meta: &ParseNestedMeta<'_>, existing: &[OnSignalAttribute]) -> Result<OnSignalAttribute, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    let mut signals = Vec::new();
+    while !content.is_empty() {
+        let signal = parse_signal_name(&content)?;
+        if signals.contains(&signal) || existing.iter().any(|attribute| attribute.signals.contains(&signal)) {
+            return Err(Error::new_spanned(signal, "Only one on_signal per signal is allowed."));
+        }
+        signals.push(signal);
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+    }
+    if signals.is_empty() {
+        return Err(meta.error("on_signal(...) requires at least one signal"));
+    }
+    meta.input.parse::<Token![=]>()?;
+    let variant: Ident = meta.input.parse()?;
+    Ok(OnSignalAttribute { signals, variant })
+}
+
+fn parse_code(meta: &ParseNestedMeta<'_>) -> Result<LitStr, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    content.parse()
+}
+
+fn parse_wrap_message(meta: &ParseNestedMeta<'_>) -> Result<WrapMessageAttribute, Error> {
+    let content;
+    parenthesized!(content in meta.input);
+    let lit: LitStr = content.parse()?;
+    let template = lit.value().replace("{{", "").replace("}}", "");
+    let regex = Regex::new(r#"\{(msg|variant)(:[^}]*)?\}"#).expect("parsing regex");
+    let mut uses_message = false;
+    let mut uses_variant = false;
+    for capture in regex.captures_iter(&template) {
+        match capture.get(1).expect("the regex always produces one capture group").as_str() {
+            "msg" => uses_message = true,
+            _ => uses_variant = true,
+        }
+    }
+    if !uses_message {
+        return Err(Error::new_spanned(lit, "wrap_msg(...) requires a {msg} placeholder for the message of the variant"));
+    }
+    Ok(WrapMessageAttribute { template_lit: lit, uses_variant })
+}
+
+fn parse_error_attribute(attribute: &Attribute, parsed: &mut TerminationAttributes) -> Result<(), Error> {
+    attribute.parse_args_with(|input: ParseStream<'_>| {
+        if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident != "transparent" || !input.is_empty() {
+                return Err(Error::new(ident.span(), "expected #[error(\"...\")] or #[error(transparent)]"));
+            }
+            parsed.transparent = true;
+        } else {
+            parsed.message = Some(parse_message_content(input)?);
+        }
+        Ok(())
+    })
+}
+
+pub fn parse_attributes(attributes: &[Attribute], error_alias: bool) -> Result<TerminationAttributes, Error> {
+    let mut found_attribute = false;
+    let mut found_error_attribute = false;
+    let mut parsed = TerminationAttributes::default();
+    for attribute in attributes {
+        if let Some(ident) = attribute.path().get_ident() {
+            if *ident == "from" {
+                return Err(Error::new_spanned(attribute, "#[from] can only be used on fields and with TerminationFull"));
+            }
+            if error_alias && *ident == "error" {
+                if found_error_attribute {
+                    return Err(Error::new(ident.span(), "only one #[error(...)] attribute per enum variant is allowed"));
+                }
+                if parsed.message.is_some() {
+                    return Err(Error::new(ident.span(), "#[error(...)] is an alias for msg(...), only one of them can be used per enum variant"));
+                }
+                found_error_attribute = true;
+                parse_error_attribute(attribute, &mut parsed)?;
+                continue;
+            }
+            if *ident != "termination" {
+                continue;
+            }
+            if found_attribute {
+                return Err(Error::new(ident.span(), "only one #[termination(...)] attribute per enum variant is allowed"));
+            }
+            found_attribute = true;
+        } else {
+            return Err(Error::new_spanned(attribute, "identifier expected"));
+        }
+        attribute.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if *ident == "msg" {
+                    if found_error_attribute {
+                        return Err(Error::new(ident.span(), "msg(...) cannot be combined with #[error(...)], only one of them can be used per enum variant"));
+                    } else if parsed.message.is_none() {
+                        parsed.message = Some(parse_message(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one msg per enum variant is allowed."));
+                    }
+                } else if *ident == "exit_code" {
+                    if parsed.exit_code.is_none() {
+                        parsed.exit_code = Some(parse_exit_code(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one exit_code per enum variant is allowed."));
+                    }
+                } else if *ident == "default_msg" {
+                    if parsed.default_message.is_none() {
+                        parsed.default_message = Some(parse_default_message(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one default_msg per enum is allowed."));
+                    }
+                } else if *ident == "wrap_msg" {
+                    if parsed.wrap_message.is_none() {
+                        parsed.wrap_message = Some(parse_wrap_message(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one wrap_msg per enum is allowed."));
+                    }
+                } else if *ident == "debug" {
+                    if parsed.debug.is_none() {
+                        parsed.debug = Some(parse_debug(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one debug per enum is allowed."));
+                    }
+                } else if *ident == "help" {
+                    if parsed.help.is_none() {
+                        parsed.help = Some(parse_message(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one help per enum variant is allowed."));
+                    }
+                } else if *ident == "detail" {
+                    if parsed.detail.is_none() {
+                        parsed.detail = Some(parse_message(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one detail per enum variant is allowed."));
+                    }
+                } else if *ident == "format" {
+                    if parsed.format.is_none() {
+                        parsed.format = Some(parse_format(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one format per enum is allowed."));
+                    }
+                } else if *ident == "code" {
+                    if parsed.code.is_none() {
+                        parsed.code = Some(parse_code(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one code per enum variant is allowed."));
+                    }
+                } else if *ident == "silent" {
+                    if parsed.silent.is_some() {
+                        return Err(Error::new(ident.span(), "Only one silent per enum variant is allowed."));
+                    }
+                    parsed.silent = Some(ident.clone());
+                    return Ok(());
+                } else if *ident == "stream" {
+                    if parsed.stream.is_none() {
+                        parsed.stream = Some(parse_stream(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one stream per enum variant is allowed."));
+                    }
+                } else if *ident == "signal" {
+                    if parsed.signal.is_none() {
+                        parsed.signal = Some(parse_signal(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one signal per enum variant is allowed."));
+                    }
+                } else if *ident == "on_exit" {
+                    parsed.on_exit.push(meta.value()?.parse()?);
+                    return Ok(());
+                } else if *ident == "panic" {
+                    if parsed.panic.is_none() {
+                        parsed.panic = Some(meta.value()?.parse()?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one panic per enum is allowed."));
+                    }
+                } else if *ident == "on_signal" {
+                    let on_signal = parse_on_signal(&meta, &parsed.on_signal)?;
+                    parsed.on_signal.push(on_signal);
+                    return Ok(());
+                } else if *ident == "broken_pipe" {
+                    if parsed.broken_pipe.is_none() {
+                        parsed.broken_pipe = Some(parse_broken_pipe(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one broken_pipe per enum is allowed."));
+                    }
+                } else if *ident == "exit_code_mode" {
+                    if parsed.exit_code_mode.is_none() {
+                        parsed.exit_code_mode = Some(parse_exit_code_mode(&meta)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one exit_code_mode per enum is allowed."));
+                    }
+                } else if *ident == "aggregate" {
+                    if parsed.aggregate.is_none() {
+                        parsed.aggregate = Some(parse_aggregate(&meta, ident)?);
+                        return Ok(());
+                    } else {
+                        return Err(Error::new(ident.span(), "Only one aggregate per enum variant is allowed."));
+                    }
+                } else if *ident == "sanitize" {
+                    if parsed.sanitize {
+                        return Err(Error::new(ident.span(), "Only one sanitize per enum is allowed."));
+                    }
+                    parsed.sanitize = true;
+                    return Ok(());
+                } else if *ident == "from" {
+                    return Err(Error::new(ident.span(), "from can only be used on fields and with TerminationFull"));
+                }
+            } else {
+                return Err(meta.error("identifier expected"));
+            }
+            Err(meta.error(format!("unrecognized attribute {}", meta.path.get_ident().expect("should never happen because of the if before"))))
+        })?;
+    }
+    Ok(parsed)
+}
+
+//I want to keep it but it is not needed anymore
+// requires syn = { version = "", features = ["full"] }
+// fn validate_format_string(lit_string: &LitStr, args: &[Expr]) -> Result<(), Error> {
+//     struct Argument<'a> {
+//         used: bool,
+//         name: String,
+//         expr: &'a Expr,
+//     }
+//     let mut used_args = Vec::new();
+//     let format_string = lit_string.value();
+//     let mut named_references = HashSet::new();
+//     let mut num_pos_references = 0;
+//     let mut unnamed_references = HashSet::new();
+//     let mut max_unnamed_reference = -1;
+//     for expr in args {
+//         if let Expr::Assign(ExprAssign { left, .. }) = expr {
+//             if let Expr::Path(path) = &**left {
+//                 if let Some(segment) = path.path.segments.first() {
+//                     used_args.push(Argument { used: false, name: segment.ident.to_string(), expr })
+//                 }
+//             }
+//         } else {
+//             used_args.push(Argument { used: false, name: "".to_string(), expr })
+//         }
+//     }
+//     let regex_unnamed = Regex::new(r#"(?:\{(?:(\d+)(?::[^\}]*)?)\})"#).expect("parsing regex");
+//     for capture in regex_unnamed.captures_iter(&format_string) {
+//         let i: i32 = capture.get(1).expect("the regex always produces one capture group").as_str().parse().expect("safe because of the regex");
+//         if i > max_unnamed_reference {
+//             max_unnamed_reference = i;
+//         }
+//         unnamed_references.insert(i);
+//     }
+//     let regex_pos = Regex::new(r#"(\{(?:(?:)?(?::[^\}]*)?)\})"#).expect("parsing regex");
+//     for _ in regex_pos.captures_iter(&format_string) {
+//         num_pos_references += 1;
+//     }
+//     let regex_named = Regex::new(r#"(?:\{(?:([^\}, :]*[a-zA-Z][^\}, :]*)(?::[^\}]*)?)\})"#).expect("parsing regex");
+//     for capture in regex_named.captures_iter(&format_string) {
+//         named_references.insert(capture.get(1).expect("the regex always produces one capture group").as_str());
+//     }
+//     if num_pos_references > used_args.len() as i32 {
+//         return Err(Error::new_spanned(lit_string, "unused positional argument"));
+//     }
+//     if max_unnamed_reference > used_args.len() as  i32 -1 {
+//         return Err(Error::new_spanned(lit_string, "out of range"));
+//     }
+//     for arg in used_args.iter_mut() {
+//         arg.used = named_references.contains(arg.name.as_str());
+//     }
+//     for reference in 0..num_pos_references {
+//         used_args[reference as usize].used = true;
+//     }
+//     for reference in unnamed_references.iter() {
+//         used_args[*reference as usize].used = true;
+//     }
+//     if let Some(arg) = used_args.iter().find(|arg| !arg.used) {
+//         return Err(Error::new_spanned(arg.expr, "unused argument"));
+//     }
+//     Ok(())
+// }
\ No newline at end of file
@@ -2,7 +2,7 @@ use syn::{DeriveInput, Data, Error};
 use proc_macro::TokenStream;
 use quote::quote;
 
-use crate::{code_generation::{generate_debug_trait, generate_termination_trait, generate_empty_debug_trait, generate_empty_termination_trait}, parse::{parse_helper_attributes, parse_attributes, Defaults}};
+use crate::{code_generation::{generate_debug_trait, generate_termination_trait, generate_empty_debug_trait, generate_empty_termination_trait}, parse::{parse_helper_attributes, parse_enum_attributes}};
 
 pub fn _derive_termination(steam: TokenStream) -> Result<TokenStream, Error> {
     let ast: DeriveInput = syn::parse(steam)?;
@@ -22,10 +22,16 @@ pub fn _derive_termination(steam: TokenStream) -> Result<TokenStream, Error> {
         return Ok(generate.into());
     }
 
-    let defaults: Defaults = parse_attributes(&ast.attrs)?.into();
-    let parsed_helper_attributes = parse_helper_attributes(variants.iter())?;
+    let defaults = parse_enum_attributes(&ast.attrs, false)?;
+    let parsed_helper_attributes = parse_helper_attributes(variants.iter(), false)?;
+    for attribute in &parsed_helper_attributes {
+        let uses_display = attribute.message.is_none() && defaults.message.is_none() && defaults.default_message.is_none();
+        if uses_display && attribute.fields.iter().any(|field| field.redact) {
+            return Err(Error::new_spanned(&attribute.variant, "a variant with redacted fields needs a msg(...), the Display message cannot be redacted"));
+        }
+    }
     let debug_trait = generate_debug_trait(name, &parsed_helper_attributes, &defaults);
-    let termination_trait = generate_termination_trait(name, &parsed_helper_attributes, &defaults);
+    let termination_trait = generate_termination_trait(name, &parsed_helper_attributes, &defaults, true)?;
     
     let generate = quote! {
         #debug_trait
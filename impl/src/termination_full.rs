@@ -2,7 +2,7 @@ use syn::{DeriveInput, Data, Error};
 use proc_macro::TokenStream;
 use quote::quote;
 
-use crate::{code_generation::{generate_termination_trait, generate_debug_trait, generate_display_trait, generate_error_trait, generate_from_traits, generate_empty_debug_trait, generate_empty_display_trait, generate_empty_termination_trait, generate_empty_error_trait}, parse::{parse_from_attribute, parse_helper_attributes, check_for_unique_types, parse_attributes, Defaults}};
+use crate::{code_generation::{generate_termination_trait, generate_debug_trait, generate_display_trait, generate_error_trait, generate_from_traits, generate_empty_debug_trait, generate_empty_display_trait, generate_empty_termination_trait, generate_empty_error_trait}, parse::{parse_from_attribute, parse_helper_attributes, check_for_unique_types, parse_enum_attributes}};
 
 pub fn _derive_termination_full(steam: TokenStream) -> Result<TokenStream, Error> {
     let ast: DeriveInput = syn::parse(steam)?;
@@ -26,14 +26,14 @@ pub fn _derive_termination_full(steam: TokenStream) -> Result<TokenStream, Error
         return Ok(generate.into());
     }
 
-    let defaults: Defaults = parse_attributes(&ast.attrs)?.into();
-    let parse_helper_attributes = parse_helper_attributes(variants.iter())?;
+    let defaults = parse_enum_attributes(&ast.attrs, true)?;
+    let parse_helper_attributes = parse_helper_attributes(variants.iter(), true)?;
     let debug_trait = generate_debug_trait(name, &parse_helper_attributes, &defaults);
     let display_trait = generate_display_trait(name, &parse_helper_attributes, &defaults)?;
-    let termination_trait = generate_termination_trait(name, &parse_helper_attributes, &defaults);
-    let error_trait = generate_error_trait(name);
+    let termination_trait = generate_termination_trait(name, &parse_helper_attributes, &defaults, true)?;
     let from_attributes = parse_from_attribute(variants.iter())?;
     check_for_unique_types(&from_attributes)?;
+    let error_trait = generate_error_trait(name, &parse_helper_attributes, &from_attributes);
     let from_traits = generate_from_traits(name, &from_attributes);
 
     let generate = quote! {
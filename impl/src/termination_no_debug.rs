@@ -0,0 +1,75 @@
+use syn::{DeriveInput, Data, Error};
+use proc_macro::TokenStream;
+use crate::{code_generation::{generate_termination_trait, generate_empty_termination_trait}, parse::{parse_helper_attributes, parse_enum_attributes}};
+use quote::quote;
+
+pub fn _derive_termination_no_debug(steam: TokenStream) -> Result<TokenStream, Error> {
+    let ast: DeriveInput = syn::parse(steam)?;
+    let name = &ast.ident;
+    let variants = match ast.data {
+        Data::Enum(ref data) => &data.variants,
+        _ => return Err(Error::new_spanned(name, "thistermination can currently only be derived on enums"))
+    };
+
+    if variants.is_empty() {
+        let termination_trait = generate_empty_termination_trait(name);
+        let generate = quote! {
+            #termination_trait
+        };
+        return Ok(generate.into());
+    }
+
+    let defaults = parse_enum_attributes(&ast.attrs, false)?;
+    if defaults.debug.is_some() {
+        return Err(Error::new_spanned(name, "unexpected debug = ... on TerminationNoDebug"))
+    }
+    if defaults.default_message.is_some() {
+        return Err(Error::new_spanned(name, "unexpected default_msg = ... on TerminationNoDebug"))
+    }
+    if defaults.wrap_message.is_some() {
+        return Err(Error::new_spanned(name, "unexpected wrap_msg(...) on TerminationNoDebug"))
+    }
+    if defaults.help.is_some() {
+        return Err(Error::new_spanned(name, "unexpected help(...) on TerminationNoDebug"))
+    }
+    if defaults.detail.is_some() {
+        return Err(Error::new_spanned(name, "unexpected detail(...) on TerminationNoDebug"))
+    }
+    if defaults.format.is_some() {
+        return Err(Error::new_spanned(name, "unexpected format = ... on TerminationNoDebug"))
+    }
+    if defaults.stream.is_some() {
+        return Err(Error::new_spanned(name, "unexpected stream = ... on TerminationNoDebug"))
+    }
+    if defaults.sanitize {
+        return Err(Error::new_spanned(name, "unexpected sanitize on TerminationNoDebug"))
+    }
+    let termination_attributes = parse_helper_attributes(variants.iter(), false)?;
+    for attribute in &termination_attributes {
+        if attribute.message.is_some() {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected msg(...) on TerminationNoDebug"))
+        }
+        if attribute.help.is_some() {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected help(...) on TerminationNoDebug"))
+        }
+        if attribute.detail.is_some() {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected detail(...) on TerminationNoDebug"))
+        }
+        if attribute.silent {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected silent on TerminationNoDebug, it never prints anything"))
+        }
+        if attribute.stream.is_some() {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected stream = ... on TerminationNoDebug"))
+        }
+        if attribute.code.is_some() {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected code(...) on TerminationNoDebug"))
+        }
+        if attribute.fields.iter().any(|field| field.redact) {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected redact on TerminationNoDebug"))
+        }
+        if attribute.fields.iter().any(|field| field.sanitize) {
+            return Err(Error::new_spanned(&attribute.variant, "unexpected sanitize on TerminationNoDebug"))
+        }
+    }
+    Ok(generate_termination_trait(name, &termination_attributes, &defaults, false)?.into())
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use std::fmt::{self, Debug, Display};
+
+use crate::{chain::TerminationError, diagnostics::Merge};
+
+/// The merged exit code of the errors of an `aggregate` variant, `fallback` if there are none.
+pub fn aggregate_exit_code<'a, I, E>(errors: &'a I, merge: Merge, fallback: u8) -> u8 where I: ?Sized, &'a I: IntoIterator<Item = &'a E>, E: TerminationError + 'a {
+    merge.merge(errors.into_iter().map(TerminationError::exit_code)).unwrap_or(fallback)
+}
+
+/// Lists the errors of an `aggregate` variant below the message, one per line.
+/// Without a message the list starts with the number of errors. Errors with multiple lines, like nested aggregates, are indented below their entry.
+pub struct Aggregate<'a, I: ?Sized>(pub &'a I, pub bool);
+
+impl<'a, I> Display for Aggregate<'a, I> where I: ?Sized, &'a I: IntoIterator, <&'a I as IntoIterator>::Item: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 {
+            match self.0.into_iter().count() {
+                1 => f.write_str("1 error:")?,
+                count => write!(f, "{} errors:", count)?,
+            }
+        }
+        for error in self.0 {
+            write!(f, "\n  - {}", format!("{:?}", error).replace('\n', "\n    "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::{any::TypeId, error::Error, fmt::{Debug, Display}, io::{self, Write}, process::{ExitCode, Termination}, sync::RwLock};
+
+/// Implemented by all derives, gives access to the exit code of a value without terminating the process.
+///
+/// Combined with [`register`] this allows [`exit_code_of`] to find derived errors behind `dyn Error`, `Box<dyn Error>` or `anyhow::Error`.
+/// Other error types can implement it by hand, only `exit_code` is required. [`Terminate`](crate::Terminate), [`ContextError`](crate::ContextError)
+/// and [`Diagnostics`](crate::Diagnostics) then exit with that exit code without printing anything, like returning an `ExitCode` from `main`.
+pub trait TerminationError {
+    /// The exit code `report()` would return for this value.
+    fn exit_code(&self) -> u8;
+
+    /// Runs the hooks, prints the termination output with `context` prepended to the message if `print` is set and returns the exit code,
+    /// used by [`Terminate`](crate::Terminate) and [`ContextError`](crate::ContextError). Overridden by the derives.
+    #[doc(hidden)]
+    fn __terminate(self, _context: &[&dyn Display], _print: bool) -> u8 where Self: Sized {
+        self.exit_code()
+    }
+
+    /// Prints the termination output with `context` prepended to the message, without running hooks or raising signals, used by [`Diagnostics`](crate::Diagnostics).
+    /// Overridden by the derives.
+    #[doc(hidden)]
+    fn __print(&self, _context: &[&dyn Display]) {}
+}
+
+type Downcast = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a dyn TerminationError>;
+
+static REGISTRY: RwLock<Vec<(TypeId, Downcast)>> = RwLock::new(Vec::new());
+
+/// Registers a derived error type, so that [`exit_code_of`] can find it in a `source()` chain. Registering a type twice has no effect.
+pub fn register<T: TerminationError + Error + 'static>() {
+    let mut registry = REGISTRY.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if registry.iter().all(|(type_id, _)| *type_id != TypeId::of::<T>()) {
+        registry.push((TypeId::of::<T>(), |error| error.downcast_ref::<T>().map(|error| error as &dyn TerminationError)));
+    }
+}
+
+/// Walks `error` and its `source()` chain and returns the exit code of the innermost registered derived error, which is the most specific one.
+/// Returns `None` if the chain contains no registered type.
+pub fn exit_code_of(error: &(dyn Error + 'static)) -> Option<u8> {
+    let registry = REGISTRY.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut exit_code = None;
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(termination_error) = registry.iter().find_map(|(_, downcast)| downcast(error)) {
+            exit_code = Some(termination_error.exit_code());
+        }
+        current = error.source();
+    }
+    exit_code
+}
+
+/// A return type for `main` that keeps the derived exit codes of errors behind `anyhow::Error`, `eyre::Report` or `Box<dyn Error>`.
+///
+/// On `Err` the error is printed like std prints an `Err` returned from `main`, and the exit code is looked up with [`exit_code_of`], defaulting to `1`.
+pub struct Exit<E>(pub Result<(), E>);
+
+impl<E> From<Result<(), E>> for Exit<E> {
+    fn from(result: Result<(), E>) -> Self {
+        Exit(result)
+    }
+}
+
+impl<E: Debug + AsRef<dyn Error + 'static>> Termination for Exit<E> {
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                // Like `Report::print`, write errors such as a closed stderr are ignored instead of panicking.
+                let _ = writeln!(io::stderr().lock(), "Error: {:?}", error);
+                ExitCode::from(exit_code_of(error.as_ref()).unwrap_or(1))
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{error::Error, fmt::{self, Debug, Display}, process::{ExitCode, Termination}};
+
+use crate::chain::TerminationError;
+
+/// Adds context to the error of a `Result` without losing its derived exit code.
+///
+/// Conflicts with `anyhow::Context` and `eyre::WrapErr` if both are imported in the same scope.
+pub trait ResultExt<T, E> {
+    /// Wraps the error in a [`ContextError`] that prepends `context` to its message.
+    fn context<C: Display>(self, context: C) -> Result<T, ContextError<E>>;
+
+    /// Like [`ResultExt::context`], but the context is only created if the result is an `Err`.
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T, ContextError<E>>;
+}
+
+impl<T, E: TerminationError> ResultExt<T, E> for Result<T, E> {
+    fn context<C: Display>(self, context: C) -> Result<T, ContextError<E>> {
+        self.map_err(|error| ContextError { context: context.to_string(), error })
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T, ContextError<E>> {
+        self.map_err(|error| ContextError { context: context().to_string(), error })
+    }
+}
+
+/// An error with context, created by [`ResultExt::context`].
+///
+/// Its message is the context followed by the message of the wrapped error, the wrapped error is its `source()`
+/// and it terminates with the exit code, help, format and hooks of the wrapped error.
+pub struct ContextError<E> {
+    context: String,
+    error: E,
+}
+
+impl<E> ContextError<E> {
+    /// The context that was added.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// The wrapped error.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the wrapped error, dropping the context.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: TerminationError> ContextError<E> {
+    /// Prints the termination output like returning `self` from `main` would, flushes stdout and stderr and exits the process with the exit code of the wrapped error.
+    pub fn exit(self) -> ! {
+        crate::exit::exit(self.__terminate(&[], true))
+    }
+}
+
+impl<E: Debug> Debug for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:?}", self.context, self.error)
+    }
+}
+
+impl<E: Display> Display for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for ContextError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<E: TerminationError> TerminationError for ContextError<E> {
+    fn exit_code(&self) -> u8 {
+        self.error.exit_code()
+    }
+
+    fn __terminate(self, context: &[&dyn Display], print: bool) -> u8 {
+        let mut context = context.to_vec();
+        context.push(&self.context);
+        self.error.__terminate(&context, print)
+    }
+
+    fn __print(&self, context: &[&dyn Display]) {
+        let mut context = context.to_vec();
+        context.push(&self.context);
+        self.error.__print(&context)
+    }
+}
+
+impl<E: TerminationError> Termination for ContextError<E> {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self.__terminate(&[], true))
+    }
+}
@@ -0,0 +1,119 @@
+use std::process::{ExitCode, Termination};
+
+use crate::chain::TerminationError;
+
+/// How [`Diagnostics`] merges the exit codes of the collected errors into the exit code of the process.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Merge {
+    /// The highest exit code, for tools whose exit codes are ordered by severity.
+    #[default]
+    Max,
+    /// The exit code of the first collected error.
+    First,
+    /// All exit codes combined with bitwise or, for tools whose exit codes are flags.
+    BitOr,
+}
+
+impl Merge {
+    /// Merges `exit_codes`, `None` if there are none.
+    pub(crate) fn merge(self, mut exit_codes: impl Iterator<Item = u8>) -> Option<u8> {
+        match self {
+            Merge::Max => exit_codes.max(),
+            Merge::First => exit_codes.next(),
+            Merge::BitOr => exit_codes.reduce(|merged, exit_code| merged | exit_code),
+        }
+    }
+}
+
+/// Collects non-fatal derived errors, so a linter or batch tool can keep going and terminate with all of them at the end.
+///
+/// Returned from `main`, every collected error is printed in the order it was pushed, with its own help, output format and stream.
+/// The process exits with the merged exit code of all errors, or `0` if none were collected.
+/// The `on_exit` hooks and `signal(...)` of the collected errors are not used.
+pub struct Diagnostics<E> {
+    errors: Vec<E>,
+    merge: Merge,
+}
+
+impl<E> Diagnostics<E> {
+    /// Creates an empty collector that exits with the highest exit code.
+    pub fn new() -> Self {
+        Self::with_merge(Merge::Max)
+    }
+
+    /// Creates an empty collector that merges the exit codes with `merge`.
+    pub fn with_merge(merge: Merge) -> Self {
+        Diagnostics { errors: Vec::new(), merge }
+    }
+
+    /// Collects `error`.
+    pub fn push(&mut self, error: E) {
+        self.errors.push(error);
+    }
+
+    /// Collects the error of `result`, if any, and returns the value otherwise.
+    pub fn record<T>(&mut self, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.push(error);
+                None
+            }
+        }
+    }
+
+    /// The collected errors, in the order they were pushed.
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns the collected errors without printing them.
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+}
+
+impl<E: TerminationError> Diagnostics<E> {
+    /// The merged exit code of the collected errors, `0` if there are none.
+    pub fn exit_code(&self) -> u8 {
+        self.merge.merge(self.errors.iter().map(TerminationError::exit_code)).unwrap_or(0)
+    }
+
+    /// Prints the collected errors like returning `self` from `main` would, flushes stdout and stderr and exits the process with the merged exit code.
+    pub fn exit(self) -> ! {
+        crate::exit::exit(self.print())
+    }
+
+    fn print(&self) -> u8 {
+        for error in &self.errors {
+            error.__print(&[]);
+        }
+        self.exit_code()
+    }
+}
+
+impl<E> Default for Diagnostics<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Extend<E> for Diagnostics<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, errors: I) {
+        self.errors.extend(errors);
+    }
+}
+
+impl<E: TerminationError> Termination for Diagnostics<E> {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self.print())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{format::source_chain, report::Report};
+
+/// Emits the termination as an error level event through `log` and/or `tracing`, depending on the enabled features.
+pub fn emit(report: &Report<'_>) {
+    let message = report.message();
+    let chain = source_chain(report);
+    let source = (!chain.is_empty()).then(|| chain.join(": "));
+    #[cfg(feature = "log")]
+    emit_log(report, &message, source.as_deref());
+    #[cfg(feature = "tracing")]
+    tracing::error!(target: "thistermination", variant = report.variant, exit_code = report.exit_code, code = report.code, source = source.as_deref(), "{}", message);
+}
+
+#[cfg(feature = "log")]
+fn emit_log(report: &Report<'_>, message: &str, source: Option<&str>) {
+    let exit_code = report.exit_code.to_string();
+    let mut fields = vec![("variant", report.variant), ("exit_code", exit_code.as_str())];
+    if let Some(code) = report.code {
+        fields.push(("code", code));
+    }
+    if let Some(source) = source {
+        fields.push(("source", source));
+    }
+    log::logger().log(&log::Record::builder()
+        .level(log::Level::Error)
+        .target("thistermination")
+        .module_path_static(Some(module_path!()))
+        .args(format_args!("{}", message))
+        .key_values(&fields.as_slice())
+        .build());
+}
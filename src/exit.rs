@@ -0,0 +1,26 @@
+use std::{cell::Cell, io::{self, Write}, panic::{self, AssertUnwindSafe}, process};
+
+thread_local! {
+    /// Set while `on_exit` hooks run, the process is already terminating and a panic in a hook must not terminate it again.
+    static TERMINATING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Flushes stdout and stderr and exits the process with `exit_code`.
+pub fn exit(exit_code: u8) -> ! {
+    // There is nowhere left to report a failed flush.
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+    process::exit(i32::from(exit_code))
+}
+
+/// Runs an `on_exit` hook. A panic inside the hook is reported by the panic hook but does not stop the termination or the remaining hooks.
+pub fn run_exit_hook(hook: impl FnOnce()) {
+    let terminating = TERMINATING.replace(true);
+    let _ = panic::catch_unwind(AssertUnwindSafe(hook));
+    TERMINATING.set(terminating);
+}
+
+/// Whether the current thread is running `on_exit` hooks.
+pub(crate) fn is_terminating() -> bool {
+    TERMINATING.get()
+}
@@ -0,0 +1,157 @@
+use std::{env, error::Error, fmt::Write as _, io::{self, Write}};
+
+use crate::report::Report;
+
+/// How the generated `report()` prints the termination output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Logfmt,
+    #[cfg(feature = "github")]
+    Github,
+}
+
+impl Format {
+    /// `THISTERMINATION_FORMAT` takes precedence over the format chosen on the enum.
+    pub fn resolve(self) -> Format {
+        match env::var("THISTERMINATION_FORMAT").as_deref() {
+            Ok("text") => Format::Text,
+            Ok("json") => Format::Json,
+            Ok("logfmt") => Format::Logfmt,
+            #[cfg(feature = "github")]
+            Ok("github") => Format::Github,
+            #[cfg(feature = "github")]
+            _ if self == Format::Text && env::var_os("GITHUB_ACTIONS").is_some_and(|value| value == "true") => Format::Github,
+            _ => self,
+        }
+    }
+}
+
+pub(crate) fn source_chain(report: &Report<'_>) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut source = report.error.and_then(Error::source);
+    while let Some(error) = source {
+        chain.push(error.to_string());
+        source = error.source();
+    }
+    chain
+}
+
+pub fn write_json(out: &mut impl Write, report: &Report<'_>) -> io::Result<()> {
+    let mut record = String::new();
+    record.push_str("{\"variant\":");
+    push_json_string(&mut record, report.variant);
+    let _ = write!(record, ",\"exit_code\":{}", report.exit_code);
+    record.push_str(",\"message\":");
+    push_json_string(&mut record, &report.message());
+    if let Some(code) = report.code {
+        record.push_str(",\"code\":");
+        push_json_string(&mut record, code);
+    }
+    if let Some(help) = &report.help {
+        record.push_str(",\"help\":");
+        push_json_string(&mut record, help);
+    }
+    let chain = source_chain(report);
+    if !chain.is_empty() {
+        record.push_str(",\"source\":[");
+        for (i, source) in chain.iter().enumerate() {
+            if i > 0 {
+                record.push(',');
+            }
+            push_json_string(&mut record, source);
+        }
+        record.push(']');
+    }
+    record.push('}');
+    writeln!(out, "{}", record)?;
+    out.flush()
+}
+
+fn push_json_string(record: &mut String, value: &str) {
+    record.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => record.push_str("\\\""),
+            '\\' => record.push_str("\\\\"),
+            '\n' => record.push_str("\\n"),
+            '\r' => record.push_str("\\r"),
+            '\t' => record.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(record, "\\u{:04x}", c as u32);
+            }
+            c => record.push(c),
+        }
+    }
+    record.push('"');
+}
+
+pub fn write_logfmt(out: &mut impl Write, report: &Report<'_>) -> io::Result<()> {
+    let mut record = String::from("level=error");
+    push_logfmt_pair(&mut record, "variant", report.variant);
+    push_logfmt_pair(&mut record, "exit_code", &report.exit_code.to_string());
+    push_logfmt_pair(&mut record, "message", &report.message());
+    if let Some(code) = report.code {
+        push_logfmt_pair(&mut record, "code", code);
+    }
+    if let Some(help) = &report.help {
+        push_logfmt_pair(&mut record, "help", help);
+    }
+    let chain = source_chain(report);
+    if !chain.is_empty() {
+        push_logfmt_pair(&mut record, "source", &chain.join(": "));
+    }
+    writeln!(out, "{}", record)?;
+    out.flush()
+}
+
+fn push_logfmt_pair(record: &mut String, key: &str, value: &str) {
+    record.push(' ');
+    record.push_str(key);
+    record.push('=');
+    if !value.is_empty() && !value.chars().any(|c| c == ' ' || c == '=' || c == '"' || c.is_control()) {
+        record.push_str(value);
+        return;
+    }
+    record.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => record.push_str("\\\""),
+            '\\' => record.push_str("\\\\"),
+            '\n' => record.push_str("\\n"),
+            '\r' => record.push_str("\\r"),
+            '\t' => record.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(record, "\\u{{{:x}}}", c as u32);
+            }
+            c => record.push(c),
+        }
+    }
+    record.push('"');
+}
+
+/// Writes the output as GitHub Actions workflow commands, so it shows up as an annotation.
+#[cfg(feature = "github")]
+pub fn write_github(out: &mut impl Write, report: &Report<'_>) -> io::Result<()> {
+    let mut message = report.message();
+    for source in source_chain(report) {
+        message.push_str("\ncaused by: ");
+        message.push_str(&source);
+    }
+    writeln!(out, "::error title={}::{}", escape_github_property(report.variant), escape_github_data(&message))?;
+    if let Some(help) = &report.help {
+        writeln!(out, "::notice title={}::{}", escape_github_property(report.variant), escape_github_data(help))?;
+    }
+    out.flush()
+}
+
+#[cfg(feature = "github")]
+fn escape_github_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+#[cfg(feature = "github")]
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value).replace(':', "%3A").replace(',', "%2C")
+}
@@ -1,14 +1,14 @@
 //! # thistermination
-//! 
+//!
 //! thistermination is a library crate inspired by [thiserror](https://crates.io/crates/thiserror) to add the [`std::process::Termination`](https://doc.rust-lang.org/std/process/trait.Termination.html) trait to error enums.
-//! 
+//!
 //! ## Usage
-//! 
+//!
 //! To add the `std::process::Termination` trait to an enum, you can use one of three possible derive macros:
-//! 
+//!
 //! - `#[derive(Termination)]`: is intended to be used in combination with thiserror, this macro implements the traits `std::process::Termination` and `std::fmt::Debug`. The `exit_code` defaults to `libc::EXIT_FAILURE`, and the Debug message is the same as the Display message unless explicitly set using `exit_code` and `msg`.
-//!   ```rust
-//!   use thistermination::{Termination};
+//!   ```rust,no_run
+//!   use thistermination::{Termination, Terminate};
 //!   use thiserror::Error;
 //!   
 //!   #[derive(Error, Termination)]
@@ -26,14 +26,14 @@
 //!       ImageLoadError{#[from] error: image::ImageError},
 //!   }
 //!   
-//!   fn main() -> Result<(), RequestError> {
-//!       Err(RequestError::WrongAPIKey)
+//!   fn main() -> Terminate<RequestError> {
+//!       Terminate(Err(RequestError::WrongAPIKey))
 //!   }
 //!   ```
-//! 
+//!
 //! - `#[derive(TerminationFull)]`: is intended to be used without thiserror, this macro implements the traits `std::process::Termination`, `std::fmt::Debug`, `std::fmt::Display`, and `std::error::Error`. The `exit_code` defaults to `libc::EXIT_FAILURE`, and `msg` is required and used for both Display and Debug.
-//!   ```rust
-//!   use thistermination::{TerminationFull};
+//!   ```rust,no_run
+//!   use thistermination::{TerminationFull, Terminate};
 //!   
 //!   #[derive(TerminationFull)]
 //!   pub enum RequestError {
@@ -47,14 +47,14 @@
 //!       ImageLoadError{#[from] error: image::ImageError},
 //!   }
 //!   
-//!   fn main() -> Result<(), RequestError> {
-//!       Err(RequestError::WrongAPIKey)
+//!   fn main() -> Terminate<RequestError> {
+//!       Terminate(Err(RequestError::WrongAPIKey))
 //!   }
 //!   ```
-//! 
-//! - `#[derive(TerminationNoDebug)]`: is the most basic variant, implementing only the `std::process::Termination` trait. If no `exit_code` is provided, it defaults to `libc::EXIT_FAILURE`. However, the `std::fmt::Debug` trait is necessary for the enum to be returned by the `main` function and must be implemented manually or using the Debug macro.
-//!   ```rust
-//!   use thistermination::{TerminationNoDebug};
+//!
+//! - `#[derive(TerminationNoDebug)]`: is the most basic variant, implementing only the `std::process::Termination` trait. If no `exit_code` is provided, it defaults to `libc::EXIT_FAILURE`. Nothing is printed on exit, and the `std::fmt::Debug` trait has to be implemented manually or using the Debug macro where it is needed.
+//!   ```rust,no_run
+//!   use thistermination::{TerminationNoDebug, Terminate};
 //!   
 //!   #[derive(TerminationNoDebug, Debug)]
 //!   pub enum RequestError {
@@ -66,24 +66,24 @@
 //!       ImageLoadError{error: image::ImageError},
 //!   }
 //!   
-//!   fn main() -> Result<(), RequestError> {
-//!       Err(RequestError::WrongAPIKey)
+//!   fn main() -> Terminate<RequestError> {
+//!       Terminate(Err(RequestError::WrongAPIKey))
 //!   }
 //!   ```
-//! 
+//!
 //! ## Details
-//! 
-//! - thistermination does not appear in your public API; the macros simply implement the the various traits.
-//! 
+//!
+//! - The macros implement the various traits, including `thistermination::TerminationError`, and add inherent functions like `exit()` to your enum, so thistermination is part of the public API of the enum and has to be a direct dependency. Types like `Terminate<E>`, `Exit<E>`, `ContextError<E>` and `Diagnostics<E>` also appear in your public API if your functions return them.
+//!
 //! - The macros can be derived for unit enums, enums with named fields, and enum tuples.
-//! 
+//!
 //! - `msg` supports accessing the fields of the enum in a format string manner
-//! 
+//!
 //!   - `#[termination(msg("{var}"))]`&ensp;⟶&ensp;`write!("{}", self.var)`
 //!   - `#[termination(msg("{0}"))]`&ensp;⟶&ensp;`write!("{}", self.0)`
 //!   - `#[termination(msg("{var:?}"))]`&ensp;⟶&ensp;`write!("{:?}", self.var)`
 //!   - `#[termination(msg("{0:?}"))]`&ensp;⟶&ensp;`write!("{:?}", self.0)`
-//! 
+//!
 //!   You can also specify additional format string arguments for `msg`
 //!   ```rust
 //!   # use thistermination::{TerminationFull};
@@ -93,24 +93,131 @@
 //!       ImageLoadError(#[from] image::ImageError),
 //!   }
 //!   ```
-//! 
-//! - Using `#[from]` will generate a `std::convert::From` implementation for the specific variant. A variant with `#[from]` is not allowed to contain any additional fields and can only be used in combination with `#[derive(TerminationFull)]`. 
-//! 
-//!   ```rust
+//!
+//! - Using `#[from]` will generate a `std::convert::From` implementation for the specific variant, and the field is returned from `source()` if it implements `std::error::Error`. A variant with `#[from]` is not allowed to contain any additional fields and can only be used in combination with `#[derive(TerminationFull)]`. 
+//!
+//!   ```rust,no_run
 //!   # use thistermination::{TerminationFull};
 //!   #[derive(TerminationFull)]
 //!   pub enum CLIError {
 //!       #[termination(exit_code(4), msg("Invalid argument {0}, expected < {}", i16::MAX))]
 //!       InvalidArgument(u16),
 //!   }
-//!   # fn main() -> Result<(), CLIError> {
-//!   #    Err(CLIError::InvalidArgument(5))
+//!   # fn main() -> thistermination::Terminate<CLIError> {
+//!   #    thistermination::Terminate(Err(CLIError::InvalidArgument(5)))
 //!   # }
 //!   ```
-//! 
-//! - You can also change the default values of `exit_code` and `msg` by adding the `#[termination(...)]` helper attribute to the enum itself.
+//!
+//! - `exit_code(from_io_kind)` picks the exit code from the `std::io::ErrorKind` of the `io::Error` held by the variant, found in its fields or their `source()` chain, using the conventional codes from `sysexits.h`: `NotFound` becomes `EX_NOINPUT` (66), `PermissionDenied` becomes `EX_NOPERM` (77), `AddrInUse` becomes `EX_UNAVAILABLE` (69), and so on, see `thistermination::exit_code_for_io_kind` for the full table. Entries can be overridden with `exit_code(from_io_kind(NotFound = 2, ...))`. If the variant holds no `io::Error`, the default exit code is used.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum FileError {
+//!       #[termination(exit_code(from_io_kind), msg("io error: {0}"))]
+//!       Io(#[from] std::io::Error),
+//!       #[termination(exit_code(from_io_kind(NotFound = 2)), msg("cannot open {path}: {source}"))]
+//!       Open { path: String, source: std::io::Error },
+//!   }
+//!   ```
+//!
+//! - Wrappers around `std::process::Command` can exit with the status of a failed child process by using `exit_code(from_status = field)` on a variant with a `std::process::ExitStatus` field. The exit code of the child is forwarded; if it was terminated by signal `n`, the exit code is `128 + n`. If the child exited with 0, the default exit code is used, so that the error does not report success.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum WrapperError {
+//!       #[termination(exit_code(from_status = status), msg("{command} failed with {status}"))]
+//!       CommandFailed { command: String, status: std::process::ExitStatus },
+//!   }
+//!   ```
+//!
+//! - A variant whose only field holds several errors, like `Many(Vec<FileError>)`, can be marked with `aggregate`. Its exit code is the highest exit code of the inner errors, `aggregate = "first"` uses the one of the first error and `aggregate = "bitor"` combines all of them with bitwise or. If there are no errors, the `exit_code` of the variant is used. The message lists each inner error on its own line below the `msg` of the variant, or below the number of errors if it has none. The inner errors have to implement `thistermination::TerminationError` and `std::fmt::Debug`, which all derives do. With `#[derive(Termination)]` only the `Debug` output lists the errors, because the `Display` implementation comes from `thiserror`.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum FileError {
+//!       #[termination(exit_code(66), msg("{0}: not found"))]
+//!       NotFound(String),
+//!   }
+//!   
+//!   #[derive(TerminationFull)]
+//!   pub enum CLIError {
+//!       #[termination(aggregate, msg("failed to read {} files", files.len()))]
+//!       Many { files: Vec<FileError> },
+//!   }
+//!   ```
+//!
+//! - Tools like `fsck` report several failure categories at once by combining bits in their exit status. With `exit_code_mode = bitflags` on the enum, every variant has to declare its own bit with `exit_code(...)`, using a power of two like 1, 2, 4 or 8. Variants that share a bit are rejected at compile time. The generated `combine(...)` ORs the bits of several errors into one `std::process::ExitCode`, and `Diagnostics` with `Merge::BitOr` does the same while also printing the errors.
+//!
+//!   ```rust,no_run
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   #[termination(exit_code_mode = bitflags)]
+//!   pub enum CheckError {
+//!       #[termination(exit_code(1), msg("errors corrected"))]
+//!       Corrected,
+//!       #[termination(exit_code(4), msg("errors left uncorrected"))]
+//!       Uncorrected,
+//!   }
+//!
+//!   fn main() -> std::process::ExitCode {
+//!       // Exits with code 5.
+//!       CheckError::combine(&[CheckError::Corrected, CheckError::Uncorrected])
+//!   }
+//!   ```
+//!
+//! - Variants that represent an interruption, like the user pressing Ctrl-C, can use `signal(SIGINT)` instead of an exit code. After the message is printed, the default handler of the signal is restored and the signal is raised again, so the parent shell sees that the process was terminated by the signal. If that does not terminate the process, e.g. on Windows, the exit code is `128 + n`. Supported are `SIGHUP`, `SIGINT`, `SIGQUIT`, `SIGABRT`, `SIGPIPE`, `SIGALRM`, `SIGTERM`, `SIGUSR1` and `SIGUSR2`.
 //!
 //!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum CLIError {
+//!       #[termination(msg("interrupted"), signal(SIGINT))]
+//!       Interrupted,
+//!   }
+//!   ```
+//!
+//! - Instead of writing your own Ctrl-C glue, `on_signal(SIGINT, SIGTERM) = Variant` on the enum generates an `install_signal_handlers()` function. Once it is called, the process terminates through the given unit variant, with its message and exit code, when one of the signals arrives. Combined with `signal(...)` on the variant, the signal is re-raised after the message is printed. `on_signal` can be used several times to map different signals to different variants. Signal handlers are only supported on Unix; elsewhere `install_signal_handlers()` returns an error.
+//!
+//!   ```rust,no_run
+//!   # use thistermination::{TerminationFull, Terminate};
+//!   #[derive(TerminationFull)]
+//!   #[termination(on_signal(SIGINT, SIGTERM) = Interrupted)]
+//!   pub enum CLIError {
+//!       #[termination(msg("interrupted"), signal(SIGINT))]
+//!       Interrupted,
+//!   }
+//!
+//!   fn main() -> Terminate<CLIError> {
+//!       CLIError::install_signal_handlers().expect("failed to install signal handlers");
+//!       // ...
+//!       Terminate(Ok(()))
+//!   }
+//!   ```
+//!
+//! - By default, a panic exits with code 101 and the message of the default panic hook. With `panic = Variant` on the enum, the generated `install_panic_hook()` replaces the panic hook with one that terminates the process through that variant, so panics follow the same exit codes and output format. The variant can be a unit variant or have the named fields `message: String`, `location: String` and `backtrace: Option<String>`, which are filled from the panic. The backtrace is only captured if enabled with `RUST_BACKTRACE`. Because the process terminates in the hook, panics can no longer be caught with `std::panic::catch_unwind`.
+//!
+//!   ```rust,no_run
+//!   # use thistermination::{TerminationFull, Terminate};
+//!   #[derive(TerminationFull)]
+//!   #[termination(panic = Internal)]
+//!   pub enum CLIError {
+//!       #[termination(exit_code(70), msg("internal error: {message} at {location}"))]
+//!       Internal { message: String, location: String },
+//!   }
+//!
+//!   fn main() -> Terminate<CLIError> {
+//!       CLIError::install_panic_hook();
+//!       // ...
+//!       Terminate(Ok(()))
+//!   }
+//!   ```
+//!
+//! - You can also change the default values of `exit_code` and `msg` by adding the `#[termination(...)]` helper attribute to the enum itself.
+//!
+//!   ```rust,no_run
 //!   # use thistermination::{Termination};
 //!   # use thiserror::Error;
 //!   #[derive(Error, Termination)]
@@ -126,53 +233,307 @@
 //!       #[error("failed to load image {error:?}")]
 //!       ImageLoadError{#[from] error: image::ImageError},
 //!   }
-//!   # fn main() -> Result<(), RequestError> {
-//!   #    Err(RequestError::WrongAPIKey)
+//!   # fn main() -> thistermination::Terminate<RequestError> {
+//!   #    thistermination::Terminate(Err(RequestError::WrongAPIKey))
 //!   # }
 //!   ```
+//!
+//! - Instead of writing a `msg` for every variant, you can let the macros derive it from the variant name by adding `default_msg = "sentence"` to the `#[termination(...)]` attribute of the enum. `WrongAPIKey` becomes "wrong api key" and the fields of the variant are appended, so `RequestStatusError(404)` becomes "request status error: 404". An explicit `msg`, either on the variant or on the enum, takes precedence.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   #[termination(default_msg = "sentence")]
+//!   pub enum RequestError {
+//!       WrongAPIKey,
+//!       RequestStatusError(u16),
+//!       #[termination(exit_code(4), msg("failed to load image {error:?}"))]
+//!       ImageLoadError{#[from] error: image::ImageError},
+//!   }
+//!   ```
+//!
+//! - To make migrating from thiserror easier, `#[derive(TerminationFull)]` also accepts `#[error("...")]` as an alias for `#[termination(msg("..."))]`. `#[error(transparent)]` forwards both the message and `source()` to the single field of the variant. Using `#[error(...)]` and `msg(...)` on the same variant is an error.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum RequestError {
+//!       #[termination(exit_code(3))]
+//!       #[error("failed with status {0}")]
+//!       RequestStatusError(u16),
+//!       #[error(transparent)]
+//!       Io(#[from] std::io::Error),
+//!   }
+//!   ```
+//!
+//! - To give every message of an enum a common shape, add `wrap_msg("...")` to the `#[termination(...)]` attribute of the enum. The template is applied to the message of every variant, `{msg}` is replaced by the message of the variant and `{variant}` by the name of the variant. The template has to contain `{msg}`. Literal braces have to be escaped as `{{` and `}}`.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   #[termination(wrap_msg("upload failed: {msg}"))]
+//!   pub enum UploadError {
+//!       #[termination(msg("wrong api key"))]
+//!       WrongAPIKey,
+//!       #[termination(exit_code(3), msg("status {0}"))]
+//!       RequestStatusError(u16),
+//!   }
+//!   ```
+//!
+//! - The generated `Debug` implementation prints the message for `{:?}` and a derive-style dump of the variant and its fields for `{:#?}`, so `dbg!()` still shows the actual field values. This can be changed by adding `debug = "message"` (always print the message), `debug = "struct"` (always print the fields), or `debug = "alternate"` (the default) to the `#[termination(...)]` attribute of the enum. With `debug = "struct"` all fields have to implement `std::fmt::Debug`. With the default, a variant with a field that does not implement `std::fmt::Debug` prints its message for `{:#?}` as well. The termination output always uses the message, whatever `debug` is set to.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   #[termination(debug = "message")]
+//!   pub enum RequestError {
+//!       #[termination(exit_code(3), msg("failed with status {0}"))]
+//!       RequestStatusError(u16),
+//!   }
+//!   ```
+//!
+//! - Fields that hold secrets can be marked with `#[termination(redact)]`. They are replaced by `[REDACTED]` wherever they appear in `msg`, `help` or the generated `Debug` output, so they never end up on the terminal or in logs. With `#[derive(Termination)]` a variant with redacted fields needs its own `msg(...)`, because the `Display` implementation of `thiserror` cannot be redacted.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum LoginError {
+//!       #[termination(exit_code(3), msg("login failed for {user} with token {token}"))]
+//!       InvalidToken { user: String, #[termination(redact)] token: String },
+//!   }
+//!   ```
+//!
+//! - Field values often come from untrusted input such as file names or HTTP bodies. Marking a field with `#[termination(sanitize)]`, or the whole enum to cover all fields, escapes control characters, including the `ESC` that starts terminal escape sequences, before the value is printed. A value like `"\x1b[2J"` is printed as `\u{1b}[2J` instead of clearing the terminal. Values without control characters are printed unchanged. This covers fields captured by the format string, like `{path}` or `{0:?}`, and arguments that use a sanitized field, like `path.display()`; the field keeps its own type there, so its methods can still be called, and the output of the argument is escaped. With `#[derive(Termination)]` only the `Debug` output is sanitized, because the `Display` implementation comes from `thiserror`.
+//!
+//!   ```rust
+//!   # use thistermination::{TerminationFull};
+//!   #[derive(TerminationFull)]
+//!   pub enum FileError {
+//!       #[termination(exit_code(4), msg("cannot open {path}"))]
+//!       OpenFailed { #[termination(sanitize)] path: String },
+//!   }
+//!   ```
+//!
+//! ## Termination Output
+//!
+//! std does not use the exit code of the variant when `main` returns `Result<(), E>`: it prints the `Err` as `Error: <Debug message>` and always exits with 1. Return `thistermination::Terminate<E>` instead, either as `Terminate(result)` or with `result.into()`. On `Err` it runs the `on_exit` hooks, writes the message of an enum derived with `#[derive(Termination)]` or `#[derive(TerminationFull)]` to stderr in the same format std uses, i.e. `Error: <message>`, and exits with the exit code of the variant. `#[derive(TerminationNoDebug)]` does not print anything.
+//!
+//! Returning the enum itself from `main`, or calling `report()` on it, runs the hooks and returns the exit code of the variant without printing anything, like thistermination 1.x did.
+//!
+//! When returning to `main` is not possible, e.g. in a worker thread or a callback, `exit()` can be called on the enum instead. It prints the message exactly as returning it in a `Terminate` from `main` would, flushes stdout and stderr and exits the process with the exit code of the variant.
+//!
+//! ```rust,no_run
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! pub enum WorkerError {
+//!     #[termination(exit_code(3), msg("worker failed: {0}"))]
+//!     Failed(String),
+//! }
+//!
+//! std::thread::spawn(|| WorkerError::Failed("disk full".to_string()).exit());
+//! ```
+//!
+//! Cleanup like removing lock files, flushing telemetry or restoring terminal modes can be attached with `on_exit = path::to::fn`, on the enum or on a variant. The hooks take `&self` and run before anything is printed, whenever the enum terminates the process: returned from `main` directly or in a `Terminate`, through `report()`, `exit()`, `install_signal_handlers()` or `install_panic_hook()`. The hooks of the variant run first, followed by the ones of the enum, each in the order they were declared. A panic inside a hook is caught, so the remaining hooks still run and the process still terminates with the exit code of the variant. The panic is reported by the panic hook that was active before `install_panic_hook()`, so it does not terminate the process a second time.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! fn remove_lock_file(_: &CLIError) {
+//!     let _ = std::fs::remove_file("/tmp/cli.lock");
+//! }
+//!
+//! #[derive(TerminationFull)]
+//! #[termination(on_exit = remove_lock_file)]
+//! pub enum CLIError {
+//!     #[termination(exit_code(3), msg("already running"))]
+//!     AlreadyRunning,
+//! }
+//! ```
+//!
+//! Errors that are passed up as `anyhow::Error`, `eyre::Report` or `Box<dyn Error>` lose their exit code, because `main` only sees the outer type. Every derived enum implements `thistermination::TerminationError`. Other error types can implement it by hand with only `exit_code`; `Terminate`, `ContextError` and `Diagnostics` then exit with that code without printing anything. After registering it with `thistermination::register::<T>()`, `thistermination::exit_code_of(&error)` finds it in the `source()` chain of any `dyn Error` and returns its exit code. If several registered errors are in the chain, the innermost one wins. Returning `thistermination::Exit<E>` from `main` does this automatically: it prints the error like `main` returning an `Err` would and exits with the code of the registered error in the chain, or 1 if there is none.
+//!
+//! ```rust,no_run
+//! # use thistermination::{TerminationFull};
+//! use anyhow::Context;
+//! use thistermination::{register, Exit};
+//!
+//! #[derive(TerminationFull)]
+//! pub enum ConfigError {
+//!     #[termination(exit_code(3), msg("config missing"))]
+//!     Missing,
+//! }
+//!
+//! fn run() -> anyhow::Result<()> {
+//!     Err(ConfigError::Missing).context("failed to load settings")?;
+//!     Ok(())
+//! }
+//!
+//! fn main() -> Exit<anyhow::Error> {
+//!     register::<ConfigError>();
+//!     run().into()
+//! }
+//! ```
+//!
+//! To add context like "while loading config /etc/app.toml" around a derived error without losing its exit code, `thistermination::ResultExt` adds `context(...)` and `with_context(|| ...)` to every `Result` whose error type is derived. The resulting `ContextError` prepends the context to the message, keeps the original error as its `source()` and terminates with the exit code, help, output format and hooks of the original error. Contexts can be nested and the outermost one is printed first. `ResultExt` conflicts with `anyhow::Context` if both are imported in the same scope.
+//!
+//! ```rust,no_run
+//! # use thistermination::{TerminationFull};
+//! use thistermination::{ContextError, ResultExt, Terminate};
+//!
+//! #[derive(TerminationFull)]
+//! pub enum ConfigError {
+//!     #[termination(exit_code(3), msg("config missing"))]
+//!     Missing,
+//! }
+//!
+//! fn load_config(path: &str) -> Result<(), ConfigError> {
+//!     Err(ConfigError::Missing)
+//! }
+//!
+//! // Prints "Error: while loading config /etc/app.toml: config missing" and exits with code 3.
+//! fn main() -> Terminate<ContextError<ConfigError>> {
+//!     let path = "/etc/app.toml";
+//!     Terminate(load_config(path).with_context(|| format!("while loading config {}", path)))
+//! }
+//! ```
+//!
+//! Linters and batch tools often collect many errors, keep going and exit with the most severe code at the end. `thistermination::Diagnostics<E>` collects derived errors with `push(...)`, `record(result)` or `extend(...)`. Returned from `main` or through `exit()`, it prints every collected error in order, with its own help, output format and stream, and exits with the merged exit code, or 0 if nothing was collected. The exit codes are merged with `Merge::Max` by default; `Diagnostics::with_merge(...)` selects `Merge::First` or `Merge::BitOr` instead. The `on_exit` hooks and `signal(...)` of the collected errors are not used.
+//!
+//! ```rust,no_run
+//! # use thistermination::{TerminationFull};
+//! use thistermination::{Diagnostics, Merge};
+//!
+//! #[derive(TerminationFull)]
+//! pub enum LintError {
+//!     #[termination(exit_code(1), msg("unused import {0}"))]
+//!     UnusedImport(String),
+//!     #[termination(exit_code(2), msg("missing docs on {0}"))]
+//!     MissingDocs(String),
+//! }
+//!
+//! fn main() -> Diagnostics<LintError> {
+//!     let mut diagnostics = Diagnostics::with_merge(Merge::BitOr);
+//!     diagnostics.push(LintError::UnusedImport("std::io".to_string()));
+//!     diagnostics.push(LintError::MissingDocs("lib.rs".to_string()));
+//!     // Prints both errors and exits with code 3.
+//!     diagnostics
+//! }
+//! ```
+//!
+//! A variant can add a hint for the user with `help(...)`, which accepts the same arguments as `msg(...)` and is printed as `help: ...` after the message. Like `msg`, it can also be set on the enum as a default for all variants.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! pub enum RequestError {
+//!     #[termination(exit_code(2), msg("wrong api key"), help("get a new key with `api login`"))]
+//!     WrongAPIKey,
+//! }
+//! ```
+//!
+//! A variant can also have a longer `detail(...)`, which accepts the same arguments as `msg(...)` and can, for example, list all field values. It replaces the message on termination if verbose output is enabled, either with `THISTERMINATION_VERBOSE=1` or by calling `thistermination::set_verbose(true)`, which takes precedence. This keeps the output short for users while the full details are one environment variable away. Like `msg`, `detail` can be set on the enum as a default for all variants. The `wrap_msg` template of the enum is applied to it as well.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! pub enum UploadError {
+//!     #[termination(exit_code(3), msg("upload failed"), detail("upload of {file} to {url} failed"))]
+//!     UploadFailed { file: String, url: String },
+//! }
+//! ```
+//!
+//! Some exit paths, like a "no changes" outcome, should only set the exit code. Variants marked with `silent` print nothing, and `stream = stdout` writes the termination output to stdout instead of stderr. `stream` can also be set on the enum as a default for all variants. Both apply to every output format and to every way the termination output is printed: `Terminate`, `exit()`, `install_signal_handlers()`, `install_panic_hook()`, `ContextError` and `Diagnostics`. They have no effect when `main` returns `Result<(), E>`, because std prints the `Err` itself.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! pub enum DiffOutcome {
+//!     #[termination(exit_code(0), msg("no changes"), silent)]
+//!     NoChanges,
+//!     #[termination(exit_code(1), msg("files differ"), stream = stdout)]
+//!     Differ,
+//! }
+//! ```
+//!
+//! When the output of a CLI is piped into a tool like `head`, writes fail with `io::ErrorKind::BrokenPipe` once the reader exits. With `broken_pipe = "silent"` on the enum, a variant that holds such an `io::Error`, either directly in a field or somewhere in the `source()` chain, terminates silently with exit status 141, like other Unix tools killed by `SIGPIPE`. `TerminationError::exit_code` returns 141 for such a value as well, so `exit_code_of`, `Exit` and `Diagnostics` use the same exit status, and `Diagnostics` does not print it.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! #[termination(broken_pipe = "silent")]
+//! pub enum OutputError {
+//!     #[termination(exit_code(3), msg("write failed: {0}"))]
+//!     Write(#[from] std::io::Error),
+//! }
+//! ```
+//!
+//! With the `color` cargo feature, the `Error:` and `help:` prefixes are colored, the `source()` chain of the error is listed as `caused by:` lines, and long messages are wrapped to the terminal width. Colors are only used if stderr is a terminal, unless `CLICOLOR_FORCE` is set; `NO_COLOR` always disables them. Output to pipes and files stays plain.
+//!
+//! ```toml
+//! [dependencies]
+//! thistermination = { version = "2.0", features = ["color"] }
+//! ```
+//!
+//! For tooling that scrapes stderr, the termination output can be a single structured record instead. Choose the format with `format = "json"` or `format = "logfmt"` on the enum, or at runtime with the `THISTERMINATION_FORMAT=text|json|logfmt` environment variable, which takes precedence. The record contains the variant name, the exit code, the message, the `source()` chain, and, if set, the `help` text and an error code given with `code("...")` on the variant.
+//!
+//! ```rust
+//! # use thistermination::{TerminationFull};
+//! #[derive(TerminationFull)]
+//! #[termination(format = "json")]
+//! pub enum RequestError {
+//!     #[termination(exit_code(3), msg("failed with status {0}"), code("E0042"))]
+//!     RequestStatusError(u16),
+//! }
+//! // {"variant":"RequestStatusError","exit_code":3,"message":"failed with status 404","code":"E0042"}
+//! ```
+//!
+//! With the `github` cargo feature, the termination output is printed as GitHub Actions workflow commands when `GITHUB_ACTIONS=true`, so failures show up as annotations: `::error title=<Variant>::<message>`, followed by a `::notice` with the `help` text. This only replaces the plain text output; setting `THISTERMINATION_FORMAT=text` opts out, and `THISTERMINATION_FORMAT=github` forces it outside of CI.
+//!
+//! So that terminations also reach structured logs, the `log` and `tracing` cargo features emit an error level record or event with the target `thistermination` whenever a derived enum terminates the process, before the `on_exit` hooks run, so a hook can still flush or shut down the logger afterwards. This includes returning the enum itself from `main`, which prints nothing. The message is the text of the event, and the fields are `variant` and `exit_code`, plus `code` and `source` when present. Silent variants and `#[derive(TerminationNoDebug)]` do not emit anything.
+//!
+//! ```toml
+//! [dependencies]
+//! thistermination = { version = "2.0", features = ["tracing"] }
+//! ```
 
-use proc_macro::TokenStream;
-use termination::_derive_termination;
-use termination_full::_derive_termination_full;
-use termination_no_debug::_derive_termination_no_debug;
-
-mod termination;
-mod termination_no_debug;
-mod termination_full;
-mod code_generation;
-mod parse;
-
-#[proc_macro_derive(Termination, attributes(termination))]
-pub fn derive_termination(steam: TokenStream) -> TokenStream {
-    match _derive_termination(steam) {
-        Ok(stream) => stream,
-        Err(err) => err.to_compile_error().into(),
-    }
-}
+pub use thistermination_impl::{Termination, TerminationFull, TerminationNoDebug};
+pub use verbose::{set_verbose, is_verbose};
+pub use sysexits::exit_code_for_io_kind;
+pub use chain::{TerminationError, register, exit_code_of, Exit};
+pub use context::{ResultExt, ContextError};
+pub use diagnostics::{Diagnostics, Merge};
+pub use terminate::Terminate;
 
-#[proc_macro_derive(TerminationNoDebug, attributes(termination))]
-pub fn derive_termination_no_debug(steam: TokenStream) -> TokenStream {
-    match _derive_termination_no_debug(steam) {
-        Ok(stream) => stream,
-        Err(err) => err.to_compile_error().into(),
-    }
-}
+mod report;
+mod format;
+mod redact;
+mod sanitize;
+mod verbose;
+mod sysexits;
+mod status;
+mod signal;
+mod panic;
+mod exit;
+mod chain;
+mod context;
+mod diagnostics;
+mod aggregate;
+mod terminate;
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod event;
+#[cfg(feature = "color")]
+mod style;
 
-#[proc_macro_derive(TerminationFull, attributes(termination, from))]
-pub fn derive_termination_full(steam: TokenStream) -> TokenStream {
-    match _derive_termination_full(steam) {
-        Ok(stream) => stream,
-        Err(err) => err.to_compile_error().into(),
-    }
+// Not public API. Used by the generated code.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::report::{Report, Message, Stream, ErrorCheck, ViaError, ViaDebug, DebugCheck, WithDebug, WithoutDebug, is_broken_pipe};
+    pub use crate::format::Format;
+    pub use crate::redact::Redacted;
+    pub use crate::sanitize::Sanitized;
+    pub use crate::sysexits::io_kind_exit_code;
+    pub use crate::status::status_exit_code;
+    pub use crate::signal::{signals, signal_exit_code, raise_signal, install_signal_handlers};
+    pub use crate::panic::{PanicDetails, install_panic_hook};
+    pub use crate::exit::{exit, run_exit_hook};
+    pub use crate::aggregate::{Aggregate, aggregate_exit_code};
 }
-
-fn pull_up_results<T, E, I>(results: I) -> Result<Vec<T>, E> where I: IntoIterator<Item = Result<T, E>> {
-    let mut items =  Vec::new();
-    for result in results {
-        match result {
-            Ok(item) => items.push(item),
-            Err(error) => return Err(error),
-        }
-    }
-    Ok(items)
-}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use std::{backtrace::{Backtrace, BacktraceStatus}, panic::{self, PanicHookInfo}};
+
+/// What the panic hook knows about a panic, used to construct the variant given with `panic = ...`.
+pub struct PanicDetails {
+    pub message: String,
+    pub location: String,
+    /// Only captured if backtraces are enabled with `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`.
+    pub backtrace: Option<String>,
+}
+
+impl PanicDetails {
+    fn new(info: &PanicHookInfo<'_>) -> PanicDetails {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+        let location = info.location().map_or_else(|| "<unknown>".to_string(), |location| location.to_string());
+        let backtrace = Backtrace::capture();
+        let backtrace = (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string());
+        PanicDetails { message, location, backtrace }
+    }
+}
+
+/// Replaces the panic hook with one that passes the details of the panic to `handler`, which is expected to terminate the process.
+/// Panics inside `on_exit` hooks are passed to the previous panic hook instead, because `handler` would run the hooks again.
+pub fn install_panic_hook(handler: fn(PanicDetails)) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if crate::exit::is_terminating() {
+            return previous(info);
+        }
+        handler(PanicDetails::new(info))
+    }));
+}
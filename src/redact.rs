@@ -0,0 +1,16 @@
+use std::fmt::{self, Debug, Display};
+
+/// Stands in for a field marked with `#[termination(redact)]` wherever the generated code would print it.
+pub struct Redacted;
+
+impl Display for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("[REDACTED]")
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("[REDACTED]")
+    }
+}
@@ -0,0 +1,164 @@
+use std::{error::Error, fmt::{self, Debug, Display}, io::{self, ErrorKind, Write}};
+
+use crate::format::{Format, write_json, write_logfmt};
+
+/// Everything the generated `report()` prints before the process terminates.
+pub struct Report<'a> {
+    pub variant: &'static str,
+    pub exit_code: u8,
+    pub message: &'a dyn Message,
+    /// Added with `ResultExt::context`, outermost first, each one is prepended to the message.
+    pub context: &'a [&'a dyn Display],
+    pub help: Option<String>,
+    /// Only set if `detail(...)` was given and verbose output is enabled, it replaces the message.
+    pub detail: Option<String>,
+    pub code: Option<&'static str>,
+    pub error: Option<&'a (dyn Error + 'static)>,
+    pub format: Format,
+    pub stream: Stream,
+    /// Set by `#[termination(silent)]`, nothing is printed.
+    pub silent: bool,
+}
+
+/// Implemented by the derives, writes the message of the variant without the struct dump `debug = "struct"` gives the `Debug` impl.
+pub trait Message {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+struct DisplayMessage<'a>(&'a dyn Message);
+
+impl Display for DisplayMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
+
+/// Where the termination output is written, selected with `stream = ...`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stream {
+    Stderr,
+    Stdout,
+}
+
+impl Report<'_> {
+    pub fn message(&self) -> String {
+        let mut message = String::new();
+        for context in self.context {
+            message.push_str(&format!("{}: ", context));
+        }
+        match &self.detail {
+            Some(detail) => message.push_str(detail),
+            None => message.push_str(&DisplayMessage(self.message).to_string()),
+        }
+        message
+    }
+
+    /// Emits the termination as a `log` record or `tracing` event, if one of the features is enabled.
+    pub fn emit(&self) {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if !self.silent {
+            crate::event::emit(self);
+        }
+    }
+
+    pub fn print(&self) {
+        if self.silent {
+            return;
+        }
+        match self.stream {
+            Stream::Stderr => self.print_to(&mut io::stderr().lock()),
+            Stream::Stdout => self.print_to(&mut io::stdout().lock()),
+        }
+    }
+
+    fn print_to(&self, out: &mut impl Write) {
+        // Errors are ignored for the same reason std ignores them when main returns an Err: there is nowhere left to report them.
+        match self.format.resolve() {
+            Format::Json => {
+                let _ = write_json(out, self);
+                return;
+            }
+            Format::Logfmt => {
+                let _ = write_logfmt(out, self);
+                return;
+            }
+            // Workflow commands go to stdout, like the ones printed by the GitHub Actions toolkit.
+            #[cfg(feature = "github")]
+            Format::Github => {
+                let _ = crate::format::write_github(&mut io::stdout().lock(), self);
+                return;
+            }
+            Format::Text => {}
+        }
+        #[cfg(feature = "color")]
+        if let Some(style) = crate::style::Style::detect(self.stream) {
+            let _ = style.write(out, self);
+            return;
+        }
+        let _ = self.write_plain(out);
+    }
+
+    fn write_plain(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "Error: {}", self.message())?;
+        if let Some(help) = &self.help {
+            writeln!(out, "help: {}", help)?;
+        }
+        out.flush()
+    }
+}
+
+/// Whether `error` or any error in its `source()` chain is an `io::Error` of kind `BrokenPipe`.
+pub fn is_broken_pipe(error: Option<&(dyn Error + 'static)>) -> bool {
+    let mut error = error;
+    while let Some(current) = error {
+        if current.downcast_ref::<io::Error>().is_some_and(|error| error.kind() == ErrorKind::BrokenPipe) {
+            return true;
+        }
+        error = current.source();
+    }
+    false
+}
+
+pub struct ErrorCheck<'a, T>(pub &'a T);
+
+/// Picked by method resolution if the enum implements `std::error::Error`, e.g. through thiserror.
+pub trait ViaError<'a> {
+    fn as_error(&self) -> Option<&'a (dyn Error + 'static)>;
+}
+
+impl<'a, T: Error + 'static> ViaError<'a> for ErrorCheck<'a, T> {
+    fn as_error(&self) -> Option<&'a (dyn Error + 'static)> {
+        Some(self.0)
+    }
+}
+
+/// Fallback for enums that do not implement `std::error::Error`.
+pub trait ViaDebug<'a> {
+    fn as_error(&self) -> Option<&'a (dyn Error + 'static)> {
+        None
+    }
+}
+
+impl<'a, T> ViaDebug<'a> for &ErrorCheck<'a, T> {}
+
+pub struct DebugCheck<'a, T>(pub &'a T);
+
+/// Picked by method resolution if the field implements `std::fmt::Debug`.
+pub trait WithDebug<'a> {
+    fn as_debug(&self) -> Option<&'a dyn Debug>;
+}
+
+impl<'a, T: Debug> WithDebug<'a> for DebugCheck<'a, T> {
+    fn as_debug(&self) -> Option<&'a dyn Debug> {
+        Some(self.0)
+    }
+}
+
+/// Fallback for fields that do not implement `std::fmt::Debug`, the `{:#?}` dump then falls back to the message.
+pub trait WithoutDebug<'a> {
+    fn as_debug(&self) -> Option<&'a dyn Debug> {
+        None
+    }
+}
+
+impl<'a, T> WithoutDebug<'a> for &DebugCheck<'a, T> {}
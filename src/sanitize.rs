@@ -0,0 +1,56 @@
+use std::fmt::{self, Debug, Display, Write};
+
+/// Wraps a field marked with `#[termination(sanitize)]` and escapes control characters in its output.
+///
+/// Values without control characters are formatted as usual, so format specs like `{:.2}` keep working.
+/// Otherwise the escaped output is written as a string, honoring width, fill, alignment and precision.
+pub struct Sanitized<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Display + ?Sized> Display for Sanitized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+        if f.alternate() {
+            write!(output, "{:#}", self.0)?;
+        } else {
+            write!(output, "{}", self.0)?;
+        }
+        if output.chars().any(needs_escape) {
+            f.pad(&escape(&output))
+        } else {
+            Display::fmt(self.0, f)
+        }
+    }
+}
+
+impl<T: Debug + ?Sized> Debug for Sanitized<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+        if f.alternate() {
+            write!(output, "{:#?}", self.0)?;
+        } else {
+            write!(output, "{:?}", self.0)?;
+        }
+        if output.chars().any(needs_escape) {
+            f.pad(&escape(&output))
+        } else {
+            Debug::fmt(self.0, f)
+        }
+    }
+}
+
+/// Control characters (including ESC, which starts terminal escape sequences, and the C1 range) and bidirectional overrides.
+fn needs_escape(c: char) -> bool {
+    c.is_control() || matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if needs_escape(c) {
+            escaped.extend(c.escape_default());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+
+/// Signals that can be used with `signal(...)`. On Unix these are the values from `libc`, elsewhere the conventional POSIX numbers, which are only used for the `128 + n` exit code.
+pub mod signals {
+    #[cfg(unix)]
+    pub use libc::{SIGHUP, SIGINT, SIGQUIT, SIGABRT, SIGPIPE, SIGALRM, SIGTERM, SIGUSR1, SIGUSR2};
+
+    #[cfg(not(unix))]
+    mod numbers {
+        pub const SIGHUP: i32 = 1;
+        pub const SIGINT: i32 = 2;
+        pub const SIGQUIT: i32 = 3;
+        pub const SIGABRT: i32 = 6;
+        pub const SIGPIPE: i32 = 13;
+        pub const SIGALRM: i32 = 14;
+        pub const SIGTERM: i32 = 15;
+        pub const SIGUSR1: i32 = 10;
+        pub const SIGUSR2: i32 = 12;
+    }
+    #[cfg(not(unix))]
+    pub use numbers::*;
+}
+
+/// The exit code a shell reports for a process terminated by `signal`.
+pub fn signal_exit_code(signal: i32) -> u8 {
+    u8::try_from(128 + signal).unwrap_or(1)
+}
+
+/// Restores the default handler of `signal`, unblocks it and raises it, which terminates the process.
+/// Only returns if the signal did not terminate the process, e.g. on platforms without signals.
+pub fn raise_signal(signal: i32) {
+    // The default handler terminates without running destructors, so nothing flushes the buffered stdout afterwards.
+    let _ = io::stdout().flush();
+    #[cfg(unix)]
+    // SAFETY: these calls only change the disposition and mask of `signal` for the current process, which is about to terminate anyway.
+    unsafe {
+        libc::signal(signal, libc::SIG_DFL);
+        let mut set: libc::sigset_t = std::mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, signal);
+        libc::pthread_sigmask(libc::SIG_UNBLOCK, &set, std::ptr::null_mut());
+        libc::raise(signal);
+    }
+    #[cfg(not(unix))]
+    let _ = signal;
+}
+
+#[cfg(unix)]
+static WRITE_FD: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(-1);
+
+#[cfg(unix)]
+extern "C" fn forward_signal(signal: libc::c_int) {
+    // Only async-signal-safe calls are allowed here, so the signal is passed on to the thread through a pipe.
+    let fd = WRITE_FD.load(std::sync::atomic::Ordering::Relaxed);
+    if fd >= 0 {
+        let byte = signal as u8;
+        // SAFETY: write is async-signal-safe and `byte` outlives the call.
+        unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+    }
+}
+
+/// Installs handlers for `signals` that call `handler` with the received signal on a separate thread.
+/// Can only be called once per process, unless it failed, in which case nothing is left installed.
+#[cfg(unix)]
+pub fn install_signal_handlers(signals: &[i32], handler: fn(i32)) -> std::io::Result<()> {
+    use std::{io, sync::atomic::Ordering, thread};
+
+    let mut fds = [0; 2];
+    // SAFETY: pipe writes two file descriptors into `fds`.
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let [read_fd, write_fd] = fds;
+    for fd in fds {
+        // SAFETY: `fd` was just created by pipe.
+        unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    }
+    if WRITE_FD.compare_exchange(-1, write_fd, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+        // SAFETY: both file descriptors were created above and are not used anywhere else.
+        unsafe {
+            libc::close(read_fd);
+            libc::close(write_fd);
+        }
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "signal handlers are already installed"));
+    }
+    // The handlers are installed before the thread is spawned, signals received in between wait in the pipe.
+    let mut previous_actions = Vec::with_capacity(signals.len());
+    for &signal in signals {
+        // SAFETY: the action is fully initialized and `forward_signal` only performs async-signal-safe operations.
+        let installed = unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = forward_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            let mut previous_action: libc::sigaction = std::mem::zeroed();
+            let installed = libc::sigaction(signal, &action, &mut previous_action) == 0;
+            if installed {
+                previous_actions.push((signal, previous_action));
+            }
+            installed
+        };
+        if !installed {
+            let error = io::Error::last_os_error();
+            uninstall_signal_handlers(&previous_actions, read_fd, write_fd);
+            return Err(error);
+        }
+    }
+    let spawned = thread::Builder::new().name("thistermination-signals".to_string()).spawn(move || loop {
+        let mut byte = 0u8;
+        // SAFETY: `read_fd` stays open for the lifetime of the process and `byte` is a valid one byte buffer.
+        let read = unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+        if read == 1 {
+            handler(i32::from(byte));
+        } else if read == 0 || io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            return;
+        }
+    });
+    if let Err(error) = spawned {
+        uninstall_signal_handlers(&previous_actions, read_fd, write_fd);
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Undoes a failed `install_signal_handlers`, so that it can be called again.
+#[cfg(unix)]
+fn uninstall_signal_handlers(previous_actions: &[(i32, libc::sigaction)], read_fd: i32, write_fd: i32) {
+    // SAFETY: the actions were returned by sigaction and both file descriptors were created by `install_signal_handlers`, no thread reads from them yet.
+    unsafe {
+        for (signal, previous_action) in previous_actions {
+            libc::sigaction(*signal, previous_action, std::ptr::null_mut());
+        }
+        WRITE_FD.store(-1, std::sync::atomic::Ordering::SeqCst);
+        libc::close(read_fd);
+        libc::close(write_fd);
+    }
+}
+
+/// Signal handlers are only supported on Unix.
+#[cfg(not(unix))]
+pub fn install_signal_handlers(_signals: &[i32], _handler: fn(i32)) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "signal handlers are only supported on Unix"))
+}
@@ -0,0 +1,17 @@
+use std::process::ExitStatus;
+
+/// The exit code for `exit_code(from_status = ...)`: the code of the child process, or `128 + n` if it was terminated by signal `n`.
+/// Returns `fallback` if the child succeeded, so that the error does not exit with 0, or if the status has no code that fits into a `u8`, e.g. on Windows.
+pub fn status_exit_code(status: &ExitStatus, fallback: u8) -> u8 {
+    if let Some(code) = status.code() {
+        return u8::try_from(code).ok().filter(|&code| code != 0).unwrap_or(fallback);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return u8::try_from(128 + signal).unwrap_or(fallback);
+        }
+    }
+    fallback
+}
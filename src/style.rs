@@ -0,0 +1,94 @@
+use std::{env, error::Error, io::{self, IsTerminal, Write}};
+
+use crate::report::{Report, Stream};
+
+const RED_BOLD: &str = "\x1b[1;31m";
+const YELLOW_BOLD: &str = "\x1b[1;33m";
+const CYAN_BOLD: &str = "\x1b[1;36m";
+const RESET: &str = "\x1b[0m";
+
+pub struct Style {
+    width: Option<usize>,
+}
+
+impl Style {
+    /// Returns `None` if the output should stay plain, following `NO_COLOR`, `CLICOLOR_FORCE` and whether `stream` is a terminal.
+    pub fn detect(stream: Stream) -> Option<Style> {
+        if !colors_enabled(stream) {
+            return None;
+        }
+        Some(Style { width: terminal_width(stream) })
+    }
+
+    pub fn write(&self, out: &mut impl Write, report: &Report<'_>) -> io::Result<()> {
+        self.write_line(out, RED_BOLD, "Error:", &report.message())?;
+        let mut source = report.error.and_then(Error::source);
+        while let Some(error) = source {
+            self.write_line(out, YELLOW_BOLD, "  caused by:", &error.to_string())?;
+            source = error.source();
+        }
+        if let Some(help) = &report.help {
+            self.write_line(out, CYAN_BOLD, "help:", help)?;
+        }
+        out.flush()
+    }
+
+    fn write_line(&self, out: &mut impl Write, color: &str, label: &str, text: &str) -> io::Result<()> {
+        writeln!(out, "{}{}{} {}", color, label, RESET, wrap(text, label.len() + 1, self.width))
+    }
+}
+
+fn colors_enabled(stream: Stream) -> bool {
+    if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    if env::var_os("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0") {
+        return true;
+    }
+    match stream {
+        Stream::Stderr => io::stderr().is_terminal(),
+        Stream::Stdout => io::stdout().is_terminal(),
+    }
+}
+
+fn terminal_width(stream: Stream) -> Option<usize> {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).filter(|columns| *columns > 0) {
+        return Some(columns);
+    }
+    #[cfg(unix)]
+    {
+        let fd = match stream {
+            Stream::Stderr => libc::STDERR_FILENO,
+            Stream::Stdout => libc::STDOUT_FILENO,
+        };
+        // SAFETY: TIOCGWINSZ only writes into the winsize struct we pass.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0 && size.ws_col > 0 {
+            return Some(size.ws_col as usize);
+        }
+    }
+    None
+}
+
+/// Wraps `text` at whitespace so that no line exceeds `width`, continuation lines are indented by `indent`.
+fn wrap(text: &str, indent: usize, width: Option<usize>) -> String {
+    let width = match width {
+        Some(width) => width.saturating_sub(indent).max(20),
+        None => return text.replace('\n', &format!("\n{}", " ".repeat(indent))),
+    };
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines.join(&format!("\n{}", " ".repeat(indent)))
+}
@@ -0,0 +1,55 @@
+use std::{error::Error, io::{self, ErrorKind}};
+
+const EX_USAGE: u8 = 64;
+const EX_DATAERR: u8 = 65;
+const EX_NOINPUT: u8 = 66;
+const EX_UNAVAILABLE: u8 = 69;
+const EX_OSERR: u8 = 71;
+const EX_CANTCREAT: u8 = 73;
+const EX_IOERR: u8 = 74;
+const EX_TEMPFAIL: u8 = 75;
+const EX_NOPERM: u8 = 77;
+
+/// Maps an `io::ErrorKind` to the conventional exit code from `sysexits.h`, as used by `exit_code(from_io_kind)`.
+///
+/// | `ErrorKind` | exit code |
+/// |---|---|
+/// | `InvalidInput` | 64 (`EX_USAGE`) |
+/// | `InvalidData`, `UnexpectedEof` | 65 (`EX_DATAERR`) |
+/// | `NotFound` | 66 (`EX_NOINPUT`) |
+/// | `ConnectionRefused`, `ConnectionReset`, `ConnectionAborted`, `NotConnected`, `AddrInUse`, `AddrNotAvailable`, `Unsupported` | 69 (`EX_UNAVAILABLE`) |
+/// | `OutOfMemory` | 71 (`EX_OSERR`) |
+/// | `AlreadyExists` | 73 (`EX_CANTCREAT`) |
+/// | `TimedOut`, `WouldBlock`, `Interrupted` | 75 (`EX_TEMPFAIL`) |
+/// | `PermissionDenied` | 77 (`EX_NOPERM`) |
+/// | everything else | 74 (`EX_IOERR`) |
+pub fn exit_code_for_io_kind(kind: ErrorKind) -> u8 {
+    match kind {
+        ErrorKind::InvalidInput => EX_USAGE,
+        ErrorKind::InvalidData | ErrorKind::UnexpectedEof => EX_DATAERR,
+        ErrorKind::NotFound => EX_NOINPUT,
+        ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::NotConnected
+            | ErrorKind::AddrInUse | ErrorKind::AddrNotAvailable | ErrorKind::Unsupported => EX_UNAVAILABLE,
+        ErrorKind::OutOfMemory => EX_OSERR,
+        ErrorKind::AlreadyExists => EX_CANTCREAT,
+        ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted => EX_TEMPFAIL,
+        ErrorKind::PermissionDenied => EX_NOPERM,
+        _ => EX_IOERR,
+    }
+}
+
+/// Looks for the first `io::Error` in the fields and their `source()` chains and maps its kind, `overrides` take precedence over the default table.
+/// Returns `fallback` if no field holds an `io::Error`.
+pub fn io_kind_exit_code(fields: &[Option<&(dyn Error + 'static)>], overrides: &[(ErrorKind, u8)], fallback: u8) -> u8 {
+    for field in fields {
+        let mut error = *field;
+        while let Some(current) = error {
+            if let Some(io_error) = current.downcast_ref::<io::Error>() {
+                let kind = io_error.kind();
+                return overrides.iter().find(|(override_kind, _)| *override_kind == kind).map_or_else(|| exit_code_for_io_kind(kind), |(_, exit_code)| *exit_code);
+            }
+            error = current.source();
+        }
+    }
+    fallback
+}
@@ -0,0 +1,25 @@
+use std::process::{ExitCode, Termination};
+
+use crate::chain::TerminationError;
+
+/// A return type for `main` that prints the error of a derived enum and exits with its exit code.
+///
+/// std prints an `Err` returned from `main` as `Error: <Debug message>` and always exits with `1`, without calling `report()` on the error.
+/// `Terminate<E>` instead terminates like `exit()` would: it runs the `on_exit` hooks, prints the termination output
+/// in the format and stream of the variant and exits with its exit code.
+pub struct Terminate<E>(pub Result<(), E>);
+
+impl<E> From<Result<(), E>> for Terminate<E> {
+    fn from(result: Result<(), E>) -> Self {
+        Terminate(result)
+    }
+}
+
+impl<E: TerminationError> Termination for Terminate<E> {
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => ExitCode::from(error.__terminate(&[], true)),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::{env, sync::atomic::{AtomicU8, Ordering}};
+
+const UNSET: u8 = 0;
+const QUIET: u8 = 1;
+const VERBOSE: u8 = 2;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Selects whether `detail(...)` is printed instead of `msg(...)` when the program terminates.
+///
+/// This takes precedence over the `THISTERMINATION_VERBOSE` environment variable.
+pub fn set_verbose(verbose: bool) {
+    VERBOSITY.store(if verbose { VERBOSE } else { QUIET }, Ordering::Relaxed);
+}
+
+/// Returns the value passed to [`set_verbose`], or whether `THISTERMINATION_VERBOSE` is set to a non-empty value other than `0`.
+pub fn is_verbose() -> bool {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        QUIET => false,
+        VERBOSE => true,
+        _ => env::var_os("THISTERMINATION_VERBOSE").is_some_and(|value| !value.is_empty() && value != "0"),
+    }
+}
@@ -0,0 +1,62 @@
+use std::process::{ExitCode, Termination as _};
+
+use thistermination::{TerminationFull, Termination, TerminationError};
+
+#[derive(TerminationFull)]
+enum FileError {
+    #[termination(exit_code(1), msg("{0}: not found"))]
+    NotFound(&'static str),
+    #[termination(exit_code(2), msg("{0}: permission denied"))]
+    PermissionDenied(&'static str),
+    #[termination(exit_code(4), msg("{0}: invalid utf-8"))]
+    InvalidUtf8(&'static str),
+}
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(aggregate)]
+    Many(Vec<FileError>),
+    #[termination(aggregate = "first", exit_code(9), msg("failed to read {} files", files.len()))]
+    First { files: Vec<FileError> },
+    #[termination(aggregate = "bitor", msg("{0:?} failed"))]
+    BitOr([FileError; 2]),
+}
+
+#[derive(Termination)]
+enum Test2 {
+    #[termination(aggregate)]
+    Many(Vec<Test>),
+}
+
+fn errors() -> Vec<FileError> {
+    vec![FileError::PermissionDenied("a.txt"), FileError::NotFound("b.txt"), FileError::InvalidUtf8("c.txt")]
+}
+
+#[test]
+fn aggregate_exit_code() {
+    assert_eq!(Test::Many(errors()).exit_code(), 4);
+    assert_eq!(Test::First { files: errors() }.exit_code(), 2);
+    assert_eq!(Test::BitOr([FileError::NotFound("a.txt"), FileError::PermissionDenied("b.txt")]).exit_code(), 3);
+    assert!(Test::Many(errors()).report() == ExitCode::from(4));
+}
+
+#[test]
+fn aggregate_fallback() {
+    assert_eq!(Test::Many(Vec::new()).exit_code(), 1);
+    assert_eq!(Test::First { files: Vec::new() }.exit_code(), 9);
+}
+
+#[test]
+fn aggregate_message() {
+    assert_eq!(format!("{}", Test::Many(errors())), "3 errors:\n  - a.txt: permission denied\n  - b.txt: not found\n  - c.txt: invalid utf-8");
+    assert_eq!(format!("{}", Test::Many(vec![FileError::NotFound("a.txt")])), "1 error:\n  - a.txt: not found");
+    assert_eq!(format!("{:?}", Test::First { files: errors() }), "failed to read 3 files\n  - a.txt: permission denied\n  - b.txt: not found\n  - c.txt: invalid utf-8");
+    assert_eq!(format!("{}", Test::BitOr([FileError::NotFound("a.txt"), FileError::PermissionDenied("b.txt")])), "[a.txt: not found, b.txt: permission denied] failed\n  - a.txt: not found\n  - b.txt: permission denied");
+}
+
+#[test]
+fn nested_aggregate() {
+    let error = Test2::Many(vec![Test::Many(vec![FileError::NotFound("a.txt")]), Test::First { files: Vec::new() }]);
+    assert_eq!(error.exit_code(), 9);
+    assert_eq!(format!("{:?}", error), "2 errors:\n  - 1 error:\n      - a.txt: not found\n  - failed to read 0 files");
+}
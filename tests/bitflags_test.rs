@@ -0,0 +1,59 @@
+use std::process::{ExitCode, Termination as _};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, TerminationNoDebug, TerminationError, Diagnostics, Merge};
+
+#[derive(TerminationFull)]
+#[termination(exit_code_mode = bitflags)]
+enum Test {
+    #[termination(exit_code(1), msg("errors corrected"))]
+    Corrected,
+    #[termination(exit_code(4), msg("errors left uncorrected in {0}"))]
+    Uncorrected(&'static str),
+    #[termination(exit_code(8), msg("operational error"))]
+    Operational { reason: String },
+}
+
+#[derive(Termination, Error)]
+#[termination(exit_code_mode = bitflags)]
+enum Test2 {
+    #[termination(exit_code(2))]
+    #[error("warnings")]
+    Warnings,
+    #[termination(exit_code(128))]
+    #[error("fatal")]
+    Fatal,
+}
+
+#[derive(TerminationNoDebug, Debug)]
+#[termination(exit_code_mode = bitflags)]
+enum Test3 {
+    #[termination(exit_code(16))]
+    Usage,
+}
+
+#[test]
+fn variant_bits() {
+    assert_eq!(Test::Corrected.exit_code(), 1);
+    assert_eq!(Test::Uncorrected("/dev/sda1").exit_code(), 4);
+    assert_eq!(Test2::Fatal.exit_code(), 128);
+    assert!(Test3::Usage.report() == ExitCode::from(16));
+}
+
+#[test]
+fn combine() {
+    let errors = [Test::Corrected, Test::Uncorrected("/dev/sda1"), Test::Corrected, Test::Operational { reason: "no space".to_string() }];
+    assert!(Test::combine(&errors) == ExitCode::from(13));
+    assert!(Test::combine(&errors[..2]) == ExitCode::from(5));
+    assert!(Test::combine(&[]) == ExitCode::SUCCESS);
+    assert!(Test2::combine(&vec![Test2::Warnings, Test2::Fatal]) == ExitCode::from(130));
+    assert!(Test3::combine(std::iter::once(&Test3::Usage)) == ExitCode::from(16));
+}
+
+#[test]
+fn diagnostics_bitor() {
+    let mut diagnostics = Diagnostics::with_merge(Merge::BitOr);
+    diagnostics.extend([Test::Uncorrected("/dev/sda1"), Test::Corrected]);
+    assert_eq!(diagnostics.exit_code(), 5);
+}
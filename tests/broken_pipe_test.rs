@@ -0,0 +1,87 @@
+use std::{io, process::{ExitCode, Termination as _}};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, TerminationNoDebug, TerminationError, Terminate, Diagnostics, Exit, register, exit_code_of};
+
+mod common;
+use common::{is_child, child_stderr};
+
+#[derive(TerminationFull)]
+#[termination(broken_pipe = "silent")]
+enum Test {
+    #[termination(exit_code(3), msg("write failed: {0}"))]
+    Write(#[from] io::Error),
+    #[termination(exit_code(4), msg("unit"))]
+    Unit,
+}
+
+#[derive(Debug, Error)]
+#[error("cannot flush output")]
+struct FlushError(#[source] io::Error);
+
+#[derive(Termination, Error)]
+#[termination(broken_pipe = "silent")]
+enum Test2 {
+    #[termination(exit_code(3), msg("flush failed"))]
+    #[error("flush failed")]
+    Flush(#[source] FlushError),
+}
+
+#[derive(TerminationNoDebug)]
+#[termination(broken_pipe = "silent")]
+enum Test3 {
+    #[termination(exit_code(3))]
+    Write(io::Error),
+}
+
+#[derive(TerminationFull)]
+enum Test4 {
+    #[termination(exit_code(3), msg("write failed: {0}"))]
+    Write(#[from] io::Error),
+}
+
+fn broken_pipe() -> io::Error {
+    io::Error::from(io::ErrorKind::BrokenPipe)
+}
+
+#[test]
+fn broken_pipe_is_silent() {
+    if is_child() {
+        assert_eq!(Test::Write(broken_pipe()).report(), ExitCode::from(141));
+        assert_eq!(Test2::Flush(FlushError(broken_pipe())).report(), ExitCode::from(141));
+        assert_eq!(Test3::Write(broken_pipe()).report(), ExitCode::from(141));
+        return;
+    }
+    assert_eq!(child_stderr("broken_pipe_is_silent", &[]), "");
+}
+
+#[test]
+fn exit_code_agrees() {
+    if is_child() {
+        register::<Test>();
+        assert_eq!(Test::Write(broken_pipe()).exit_code(), 141);
+        assert_eq!(Test2::Flush(FlushError(broken_pipe())).exit_code(), 141);
+        assert_eq!(Test3::Write(broken_pipe()).exit_code(), 141);
+        assert_eq!(Test::Write(io::Error::from(io::ErrorKind::NotFound)).exit_code(), 3);
+        assert_eq!(exit_code_of(&Test::Write(broken_pipe())), Some(141));
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Test::Write(broken_pipe()));
+        assert_eq!(diagnostics.exit_code(), 141);
+        assert_eq!(diagnostics.report(), ExitCode::from(141));
+        assert_eq!(Exit::<Box<dyn std::error::Error>>(Err(Box::new(Test::Write(broken_pipe())))).report(), ExitCode::from(141));
+        return;
+    }
+    assert_eq!(child_stderr("exit_code_agrees", &[]), "Error: write failed: broken pipe\n");
+}
+
+#[test]
+fn other_errors_are_printed() {
+    if is_child() {
+        assert_eq!(Terminate(Err(Test::Write(io::Error::from(io::ErrorKind::NotFound)))).report(), ExitCode::from(3));
+        assert_eq!(Terminate(Err(Test::Unit)).report(), ExitCode::from(4));
+        assert_eq!(Terminate(Err(Test4::Write(broken_pipe()))).report(), ExitCode::from(3));
+        return;
+    }
+    assert_eq!(child_stderr("other_errors_are_printed", &[]), "Error: write failed: entity not found\nError: unit\nError: write failed: broken pipe\n");
+}
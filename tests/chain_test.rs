@@ -0,0 +1,124 @@
+use std::{error::Error, process::{ExitCode, Termination as _}};
+
+use anyhow::Context;
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, TerminationError, register, exit_code_of, Exit, Terminate};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("config missing"))]
+    ConfigMissing,
+    #[termination(exit_code(4), msg("request failed"))]
+    RequestFailed,
+}
+
+#[derive(Termination, Error)]
+enum Test2 {
+    #[termination(exit_code(5))]
+    #[error("startup failed")]
+    Startup(#[source] Test),
+}
+
+#[derive(Debug, Error)]
+#[error("not registered")]
+struct Unregistered;
+
+fn register_all() {
+    register::<Test>();
+    register::<Test2>();
+}
+
+#[test]
+fn exit_code_of_trait_object() {
+    register_all();
+    let error: &dyn TerminationError = &Test::RequestFailed;
+    assert_eq!(error.exit_code(), 4);
+    let error: Box<dyn Error> = Box::new(Test::ConfigMissing);
+    assert_eq!(exit_code_of(error.as_ref()), Some(3));
+    assert_eq!(exit_code_of(&Unregistered), None);
+}
+
+#[test]
+fn exit_code_of_anyhow_chain() {
+    register_all();
+    let error = anyhow::Error::new(Test::RequestFailed).context("while syncing").context("sync command failed");
+    assert_eq!(exit_code_of(error.as_ref()), Some(4));
+    let error: Result<(), Test2> = Err(Test2::Startup(Test::ConfigMissing));
+    let error = error.context("starting").unwrap_err();
+    assert_eq!(exit_code_of(error.as_ref()), Some(3));
+}
+
+#[test]
+fn exit_wrapper_fallback() {
+    register_all();
+    assert!(Exit::<anyhow::Error>(Ok(())).report() == ExitCode::SUCCESS);
+    let unregistered: anyhow::Result<()> = Err(Unregistered.into());
+    assert!(Exit::from(unregistered).report() == ExitCode::from(1));
+}
+
+// The child returns Exit like main would and exits with the resulting code, so the parent can look at how it terminated.
+fn exit_with(code: ExitCode) -> ! {
+    // ExitCode can only be compared, not converted back into a number.
+    let code = (0..=u8::MAX).find(|&value| ExitCode::from(value) == code).expect("exit code in range");
+    std::process::exit(code.into())
+}
+
+fn run() -> anyhow::Result<()> {
+    Err(Test::ConfigMissing).context("failed to load settings")?;
+    Ok(())
+}
+
+fn main_like() -> Exit<anyhow::Error> {
+    register_all();
+    run().into()
+}
+
+#[test]
+fn exit_wrapper() {
+    if is_child() {
+        exit_with(main_like().report());
+    }
+    let output = child_output("exit_wrapper", &[]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: failed to load settings\n\nCaused by:\n    config missing\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn exit_wrapper_broken_stderr() {
+    if is_child() {
+        // Writes to stderr fail with a broken pipe once the read end is closed.
+        let mut fds = [0; 2];
+        unsafe {
+            assert_eq!(libc::pipe(fds.as_mut_ptr()), 0);
+            libc::dup2(fds[1], libc::STDERR_FILENO);
+            libc::close(fds[0]);
+        }
+        exit_with(main_like().report());
+    }
+    let output = child_output("exit_wrapper_broken_stderr", &[]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+struct HandWritten;
+
+impl TerminationError for HandWritten {
+    fn exit_code(&self) -> u8 {
+        7
+    }
+}
+
+#[test]
+fn hand_written_impl() {
+    if is_child() {
+        assert_eq!(Terminate(Err(HandWritten)).report(), ExitCode::from(7));
+        return;
+    }
+    let output = child_output("hand_written_impl", &[]);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,40 @@
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use std::{env, process::{Command, Output}};
+
+// Every test that terminates the process runs itself again as a child process, so the child can terminate
+// and the parent can look at the output and at how it terminated.
+pub fn is_child() -> bool {
+    env::var_os("THISTERMINATION_TEST_CHILD").is_some()
+}
+
+/// Runs `test` in a child process with `vars` set, after removing the variables that change the termination output.
+pub fn child_output(test: &str, vars: &[(&str, &str)]) -> Output {
+    Command::new(env::current_exe().expect("test executable"))
+        .args([test, "--exact", "--nocapture", "--test-threads=1", "-q"])
+        .env("THISTERMINATION_TEST_CHILD", "1")
+        .env_remove("GITHUB_ACTIONS")
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("COLUMNS")
+        .env_remove("THISTERMINATION_FORMAT")
+        .env_remove("THISTERMINATION_VERBOSE")
+        .env_remove("RUST_BACKTRACE")
+        .env_remove("RUST_LIB_BACKTRACE")
+        .envs(vars.iter().copied())
+        .output()
+        .expect("running test executable")
+}
+
+pub fn child_stderr(test: &str, vars: &[(&str, &str)]) -> String {
+    String::from_utf8(child_output(test, vars).stderr).expect("utf-8 stderr")
+}
+
+/// Like [`child_output`], but without the lines the test harness prints to stdout.
+pub fn child_stdout_and_stderr(test: &str, vars: &[(&str, &str)]) -> (String, String) {
+    let Output { stdout, stderr, .. } = child_output(test, vars);
+    let stdout = String::from_utf8(stdout).expect("utf-8 stdout");
+    let stdout = stdout.lines().filter(|line| !line.is_empty() && !line.starts_with("running ") && !line.starts_with("test result") && *line != ".").map(|line| format!("{}\n", line)).collect();
+    (stdout, String::from_utf8(stderr).expect("utf-8 stderr"))
+}
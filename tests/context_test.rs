@@ -0,0 +1,84 @@
+use std::{error::Error, process::{ExitCode, Termination as _}};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, TerminationError, ResultExt, ContextError, Terminate, register, exit_code_of};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("config missing"), help("create it with `app init`"))]
+    ConfigMissing,
+}
+
+#[derive(Termination, Error)]
+#[termination(format = "json")]
+enum Test2 {
+    #[termination(exit_code(4), msg("connection refused"))]
+    #[error("connection refused")]
+    ConnectionRefused,
+}
+
+fn load_config(path: &str) -> Result<(), ContextError<Test>> {
+    Err(Test::ConfigMissing).with_context(|| format!("while loading config {}", path))
+}
+
+#[test]
+fn context_message() {
+    let error = load_config("/etc/app.toml").unwrap_err();
+    assert_eq!(error.context(), "while loading config /etc/app.toml");
+    assert_eq!(format!("{:?}", error), "while loading config /etc/app.toml: config missing");
+    assert_eq!(format!("{}", error), "while loading config /etc/app.toml: config missing");
+    assert_eq!(error.source().map(ToString::to_string).as_deref(), Some("config missing"));
+    assert_eq!(error.exit_code(), 3);
+    let error = Err::<(), _>(Test2::ConnectionRefused).context("while syncing").context("sync failed").unwrap_err();
+    assert_eq!(format!("{}", error), "sync failed: while syncing: connection refused");
+    assert_eq!(error.exit_code(), 4);
+    assert!(matches!(error.into_inner().into_inner(), Test2::ConnectionRefused));
+}
+
+#[test]
+fn with_context_is_lazy() {
+    let result: Result<(), Test> = Ok(());
+    assert!(result.with_context(|| -> String { unreachable!("context created for Ok") }).is_ok());
+}
+
+#[test]
+fn context_in_chain() {
+    register::<Test2>();
+    let error: Box<dyn Error> = Box::new(Err::<(), _>(Test2::ConnectionRefused).context("while syncing").unwrap_err());
+    assert_eq!(exit_code_of(error.as_ref()), Some(4));
+}
+
+#[test]
+fn exit_with_context() {
+    if is_child() {
+        load_config("/etc/app.toml").context("while starting").unwrap_err().exit();
+    }
+    let output = child_output("exit_with_context", &[]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: while starting: while loading config /etc/app.toml: config missing\nhelp: create it with `app init`\n");
+}
+
+#[test]
+fn exit_with_context_json() {
+    if is_child() {
+        Err::<(), _>(Test2::ConnectionRefused).context("while syncing").unwrap_err().exit();
+    }
+    let output = child_output("exit_with_context_json", &[]);
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "{\"variant\":\"ConnectionRefused\",\"exit_code\":4,\"message\":\"while syncing: connection refused\"}\n");
+}
+
+#[test]
+fn terminate_with_context() {
+    if is_child() {
+        assert_eq!(Terminate(load_config("/etc/app.toml")).report(), ExitCode::from(3));
+        return;
+    }
+    let output = child_output("terminate_with_context", &[]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: while loading config /etc/app.toml: config missing\nhelp: create it with `app init`\n");
+}
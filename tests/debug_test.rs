@@ -0,0 +1,83 @@
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(msg("unit a"))]
+    UnitA,
+    #[termination(msg("unnamed {0}"))]
+    UnnamedA(u8, &'static str),
+    #[termination(msg("named {x}"))]
+    NamedA{x: u32, y: Option<u8>},
+}
+
+struct NoDebug(&'static str);
+
+impl std::fmt::Display for NoDebug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[derive(TerminationFull)]
+enum Test4 {
+    #[termination(msg("bad {0}"))]
+    UnnamedA(NoDebug),
+    #[termination(msg("bad {x}"))]
+    NamedA{x: NoDebug, y: u8},
+    #[termination(msg("fine {0}"))]
+    UnnamedB(u8),
+}
+
+#[derive(TerminationFull)]
+#[termination(debug = "struct")]
+enum Test2 {
+    #[termination(msg("unnamed {0}"))]
+    UnnamedA(u8),
+}
+
+#[derive(Termination, Error)]
+#[termination(debug = "message")]
+enum Test3 {
+    #[error("unnamed {0}")]
+    UnnamedA(u8),
+}
+
+#[test]
+fn alternate_debug() {
+    let unit = Test::UnitA;
+    assert_eq!(format!("{:?}", unit), "unit a");
+    assert_eq!(format!("{:#?}", unit), "UnitA");
+    let unnamed = Test::UnnamedA(42, "abc");
+    assert_eq!(format!("{:?}", unnamed), "unnamed 42");
+    assert_eq!(format!("{:#?}", unnamed), "UnnamedA(\n    42,\n    \"abc\",\n)");
+    let named = Test::NamedA{x: 1337, y: None};
+    assert_eq!(format!("{:?}", named), "named 1337");
+    assert_eq!(format!("{:#?}", named), "NamedA {\n    x: 1337,\n    y: None,\n}");
+}
+
+#[test]
+fn struct_debug() {
+    let unnamed = Test2::UnnamedA(42);
+    assert_eq!(format!("{:?}", unnamed), "UnnamedA(42)");
+    assert_eq!(format!("{}", unnamed), "unnamed 42");
+}
+
+#[test]
+fn message_debug() {
+    let unnamed = Test3::UnnamedA(42);
+    assert_eq!(format!("{:?}", unnamed), "unnamed 42");
+    assert_eq!(format!("{:#?}", unnamed), "unnamed 42");
+}
+
+#[test]
+fn alternate_debug_without_debug_fields() {
+    let unnamed = Test4::UnnamedA(NoDebug("input"));
+    assert_eq!(format!("{:?}", unnamed), "bad input");
+    assert_eq!(format!("{:#?}", unnamed), "bad input");
+    let named = Test4::NamedA{x: NoDebug("input"), y: 1};
+    assert_eq!(format!("{:#?}", named), "bad input");
+    let debug = Test4::UnnamedB(42);
+    assert_eq!(format!("{:#?}", debug), "UnnamedB(\n    42,\n)");
+}
@@ -0,0 +1,68 @@
+use std::process::{Termination, ExitCode};
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+#[derive(TerminationFull)]
+#[termination(default_msg = "sentence")]
+enum Test {
+    WrongAPIKey,
+    RequestStatusError(u16),
+    #[termination(exit_code(3))]
+    InvalidRange{start: u32, end: u32},
+    #[termination(msg("explicit message"))]
+    ExplicitMessage,
+}
+
+#[derive(Termination, Error)]
+#[termination(default_msg = "sentence")]
+enum Test2 {
+    #[error("display message")]
+    Http2Failure(u16),
+}
+
+#[derive(TerminationFull)]
+#[termination(default_msg = "sentence", msg("fallback"))]
+enum Test3 {
+    UnitA,
+}
+
+fn assert_eq_exit_code_and_int(ex: ExitCode, code: i32) {
+    assert_eq!(format!("{:?}", ex), format!("ExitCode(unix_exit_status({}))", code));
+}
+
+fn assert_eq_exit_code_and_exit_code(ex1: ExitCode, ex2: ExitCode) {
+    assert_eq!(format!("{:?}", ex1), format!("{:?}", ex2));
+}
+
+#[test]
+fn unit_default_msg() {
+    let unit = Test::WrongAPIKey;
+    assert_eq!(format!("{:?}", unit), "wrong api key");
+    assert_eq!(format!("{}", unit), "wrong api key");
+    assert_eq_exit_code_and_exit_code(unit.report(), ExitCode::FAILURE);
+    let explicit = Test::ExplicitMessage;
+    assert_eq!(format!("{}", explicit), "explicit message");
+}
+
+#[test]
+fn unnamed_default_msg() {
+    let unnamed = Test::RequestStatusError(404);
+    assert_eq!(format!("{:?}", unnamed), "request status error: 404");
+    assert_eq!(format!("{}", unnamed), "request status error: 404");
+    let unnamed = Test2::Http2Failure(500);
+    assert_eq!(format!("{:?}", unnamed), "http2 failure: 500");
+    assert_eq!(format!("{}", unnamed), "display message");
+}
+
+#[test]
+fn named_default_msg() {
+    let named = Test::InvalidRange{start: 7, end: 3};
+    assert_eq!(format!("{:?}", named), "invalid range: 7, 3");
+    assert_eq_exit_code_and_int(named.report(), 3);
+}
+
+#[test]
+fn enum_msg_takes_precedence() {
+    assert_eq!(format!("{}", Test3::UnitA), "fallback");
+}
@@ -0,0 +1,98 @@
+use std::process::Termination as _;
+
+use thistermination::{TerminationFull, set_verbose, is_verbose, Terminate};
+
+mod common;
+use common::{is_child, child_stderr};
+
+#[derive(TerminationFull)]
+#[termination(detail("no details available"))]
+enum Test {
+    #[termination(msg("upload failed"), detail("upload of {file} ({size} bytes) to {url} failed"))]
+    UploadFailed{file: &'static str, size: u64, url: &'static str},
+    #[termination(msg("download failed"), detail("download from {0} failed after {1} retries"))]
+    DownloadFailed(&'static str, u8),
+    #[termination(msg("cancelled"))]
+    Cancelled,
+}
+
+#[derive(TerminationFull)]
+enum Test2 {
+    #[termination(msg("login failed"), detail("login failed for {user} with {token}"))]
+    LoginFailed{user: &'static str, #[termination(redact)] token: &'static str},
+}
+
+#[derive(TerminationFull)]
+#[termination(wrap_msg("{variant}: upload failed: {msg}"))]
+enum Test3 {
+    #[termination(msg("timeout"), detail("timeout after {0} seconds"))]
+    Timeout(u8),
+}
+
+#[test]
+fn short_message_by_default() {
+    if is_child() {
+        assert!(!is_verbose());
+        Terminate(Err(Test::UploadFailed{file: "a.txt", size: 42, url: "https://example.com"})).report();
+        return;
+    }
+    assert_eq!(child_stderr("short_message_by_default", &[]), "Error: upload failed\n");
+    assert_eq!(child_stderr("short_message_by_default", &[("THISTERMINATION_VERBOSE", "0")]), "Error: upload failed\n");
+}
+
+#[test]
+fn detail_from_env() {
+    if is_child() {
+        Terminate(Err(Test::UploadFailed{file: "a.txt", size: 42, url: "https://example.com"})).report();
+        Terminate(Err(Test::DownloadFailed("https://example.com", 3))).report();
+        Terminate(Err(Test::Cancelled)).report();
+        return;
+    }
+    assert_eq!(child_stderr("detail_from_env", &[("THISTERMINATION_VERBOSE", "1")]),
+        "Error: upload of a.txt (42 bytes) to https://example.com failed\nError: download from https://example.com failed after 3 retries\nError: no details available\n");
+}
+
+#[test]
+fn detail_from_setter() {
+    if is_child() {
+        set_verbose(true);
+        Terminate(Err(Test::DownloadFailed("https://example.com", 3))).report();
+        set_verbose(false);
+        Terminate(Err(Test::DownloadFailed("https://example.com", 3))).report();
+        return;
+    }
+    assert_eq!(child_stderr("detail_from_setter", &[("THISTERMINATION_VERBOSE", "1")]),
+        "Error: download from https://example.com failed after 3 retries\nError: download failed\n");
+}
+
+#[test]
+fn detail_is_redacted() {
+    if is_child() {
+        set_verbose(true);
+        Terminate(Err(Test2::LoginFailed{user: "admin", token: "hunter2"})).report();
+        return;
+    }
+    assert_eq!(child_stderr("detail_is_redacted", &[]), "Error: login failed for admin with [REDACTED]\n");
+}
+
+#[test]
+fn detail_json() {
+    if is_child() {
+        Terminate(Err(Test::Cancelled)).report();
+        return;
+    }
+    assert_eq!(child_stderr("detail_json", &[("THISTERMINATION_VERBOSE", "1"), ("THISTERMINATION_FORMAT", "json")]),
+        "{\"variant\":\"Cancelled\",\"exit_code\":1,\"message\":\"no details available\"}\n");
+}
+
+#[test]
+fn detail_is_wrapped() {
+    if is_child() {
+        set_verbose(true);
+        Terminate(Err(Test3::Timeout(5))).report();
+        set_verbose(false);
+        Terminate(Err(Test3::Timeout(5))).report();
+        return;
+    }
+    assert_eq!(child_stderr("detail_is_wrapped", &[]), "Error: Timeout: upload failed: timeout after 5 seconds\nError: Timeout: upload failed: timeout\n");
+}
@@ -0,0 +1,72 @@
+use thistermination::{TerminationFull, Diagnostics, Merge, ResultExt, ContextError};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(1), msg("unused import {0}"))]
+    UnusedImport(&'static str),
+    #[termination(exit_code(2), msg("missing docs on {0}"), help("add a doc comment"))]
+    MissingDocs(&'static str),
+    #[termination(exit_code(4), msg("syntax error"))]
+    SyntaxError,
+}
+
+fn diagnostics(merge: Merge) -> Diagnostics<Test> {
+    let mut diagnostics = Diagnostics::with_merge(merge);
+    diagnostics.extend([Test::MissingDocs("lib.rs"), Test::UnusedImport("std::io"), Test::SyntaxError]);
+    diagnostics
+}
+
+#[test]
+fn merged_exit_code() {
+    assert_eq!(Diagnostics::<Test>::new().exit_code(), 0);
+    assert_eq!(diagnostics(Merge::Max).exit_code(), 4);
+    assert_eq!(diagnostics(Merge::First).exit_code(), 2);
+    assert_eq!(diagnostics(Merge::BitOr).exit_code(), 7);
+}
+
+#[test]
+fn record() {
+    let mut diagnostics = Diagnostics::default();
+    assert_eq!(diagnostics.record(Ok(5)), Some(5));
+    assert_eq!(diagnostics.record(Err::<i32, _>(Test::SyntaxError)), None);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics.errors(), [Test::SyntaxError]));
+    assert!(matches!(diagnostics.into_errors().as_slice(), [Test::SyntaxError]));
+}
+
+#[test]
+fn exit_prints_all() {
+    if is_child() {
+        diagnostics(Merge::Max).exit();
+    }
+    let output = child_output("exit_prints_all", &[]);
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: missing docs on lib.rs\nhelp: add a doc comment\nError: unused import std::io\nError: syntax error\n");
+}
+
+#[test]
+fn exit_empty() {
+    if is_child() {
+        Diagnostics::<Test>::new().exit();
+    }
+    let output = child_output("exit_empty", &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "");
+}
+
+#[test]
+fn exit_with_context() {
+    if is_child() {
+        let mut diagnostics: Diagnostics<ContextError<Test>> = Diagnostics::with_merge(Merge::BitOr);
+        for file in ["a.rs", "b.rs"] {
+            diagnostics.record(Err::<(), _>(Test::UnusedImport("std::fs")).with_context(|| format!("in {}", file)));
+        }
+        diagnostics.exit();
+    }
+    let output = child_output("exit_with_context", &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: in a.rs: unused import std::fs\nError: in b.rs: unused import std::fs\n");
+}
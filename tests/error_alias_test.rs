@@ -0,0 +1,74 @@
+use std::{process::{Termination, ExitCode}, num::ParseIntError, error::Error, fmt::{self, Display}};
+
+use thistermination::TerminationFull;
+
+#[derive(Debug)]
+struct Inner {
+    source: ParseIntError,
+}
+
+impl Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "inner error")
+    }
+}
+
+impl Error for Inner {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(TerminationFull)]
+enum Test {
+    #[error("unit a")]
+    UnitA,
+    #[termination(exit_code(3))]
+    #[error("unnamed {0:?} {}", 69)]
+    UnnamedA(u8),
+    #[error("named {x}")]
+    NamedA{x: u32},
+    #[termination(exit_code(4))]
+    #[error(transparent)]
+    Transparent(#[from] Inner),
+    #[error(transparent)]
+    TransparentNamed{inner: Inner},
+}
+
+fn assert_eq_exit_code_and_int(ex: ExitCode, code: i32) {
+    assert_eq!(format!("{:?}", ex), format!("ExitCode(unix_exit_status({}))", code));
+}
+
+fn assert_eq_exit_code_and_exit_code(ex1: ExitCode, ex2: ExitCode) {
+    assert_eq!(format!("{:?}", ex1), format!("{:?}", ex2));
+}
+
+fn inner() -> Inner {
+    Inner { source: "abc".parse::<u8>().unwrap_err() }
+}
+
+#[test]
+fn error_message() {
+    let unit_a = Test::UnitA;
+    assert_eq!(format!("{:?}", unit_a), "unit a");
+    assert_eq!(format!("{}", unit_a), "unit a");
+    assert!(unit_a.source().is_none());
+    assert_eq_exit_code_and_exit_code(unit_a.report(), ExitCode::FAILURE);
+    let unnamed_a = Test::UnnamedA(42);
+    assert_eq!(format!("{}", unnamed_a), "unnamed 42 69");
+    assert_eq_exit_code_and_int(unnamed_a.report(), 3);
+    let named_a = Test::NamedA{x: 1337};
+    assert_eq!(format!("{}", named_a), "named 1337");
+}
+
+#[test]
+fn error_transparent() {
+    let transparent: Test = inner().into();
+    assert_eq!(format!("{}", transparent), "inner error");
+    assert_eq!(format!("{:?}", transparent), "inner error");
+    assert_eq!(transparent.source().map(|source| source.to_string()), Some("invalid digit found in string".to_string()));
+    assert_eq_exit_code_and_int(transparent.report(), 4);
+    let transparent_named = Test::TransparentNamed{inner: inner()};
+    assert_eq!(format!("{}", transparent_named), "inner error");
+    assert!(transparent_named.source().is_some());
+}
@@ -0,0 +1,44 @@
+use std::{io::Write, thread};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("worker failed: {0}"), help("check the logs"))]
+    WorkerFailed(&'static str),
+}
+
+#[derive(Termination, Error)]
+enum Test2 {
+    #[termination(exit_code(4), msg("callback failed"))]
+    #[error("callback failed")]
+    CallbackFailed,
+}
+
+#[test]
+fn exit_from_thread() {
+    if is_child() {
+        thread::spawn(|| Test::WorkerFailed("disk full").exit()).join().expect("exit never returns");
+    }
+    let output = child_output("exit_from_thread", &[]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: worker failed: disk full\nhelp: check the logs\n");
+}
+
+#[test]
+fn exit_flushes_stdout() {
+    if is_child() {
+        let mut stdout = std::io::stdout();
+        write!(stdout, "partial line").expect("writing to stdout");
+        Test2::CallbackFailed.exit();
+    }
+    let output = child_output("exit_flushes_stdout", &[]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8(output.stdout).expect("utf-8 stdout").ends_with("partial line"));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: callback failed\n");
+}
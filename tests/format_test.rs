@@ -0,0 +1,113 @@
+use std::{io, process::Termination as _, num::ParseIntError};
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, Terminate};
+
+mod common;
+use common::{is_child, child_stderr};
+
+#[derive(TerminationFull)]
+#[termination(format = "json")]
+enum Test {
+    #[termination(exit_code(3), msg("unit \"a\"\n"), code("E0042"))]
+    UnitA,
+    #[termination(msg("unnamed {0}"), help("try again"))]
+    UnnamedA(u8),
+}
+
+#[derive(Termination, Error)]
+#[termination(format = "logfmt")]
+enum Test2 {
+    #[termination(exit_code(4), code("E7"))]
+    #[error("failed to parse {value}")]
+    NamedA{value: &'static str, #[source] source: ParseIntError},
+}
+
+#[derive(TerminationFull)]
+enum Test3 {
+    #[termination(exit_code(5), msg("unit a"))]
+    UnitA,
+}
+
+#[derive(TerminationFull)]
+#[termination(debug = "struct")]
+enum Test4 {
+    #[termination(exit_code(6), msg("status {0}"))]
+    Status(u16),
+}
+
+#[derive(TerminationFull)]
+#[termination(format = "json")]
+enum Test5 {
+    #[termination(exit_code(7), msg("cannot read config"))]
+    Read(#[from] io::Error),
+}
+
+#[test]
+fn json_output() {
+    if is_child() {
+        Terminate(Err(Test::UnitA)).report();
+        return;
+    }
+    assert_eq!(child_stderr("json_output", &[]), "{\"variant\":\"UnitA\",\"exit_code\":3,\"message\":\"unit \\\"a\\\"\\n\",\"code\":\"E0042\"}\n");
+}
+
+#[test]
+fn json_output_with_help() {
+    if is_child() {
+        Terminate(Err(Test::UnnamedA(42))).report();
+        return;
+    }
+    assert_eq!(child_stderr("json_output_with_help", &[]), "{\"variant\":\"UnnamedA\",\"exit_code\":1,\"message\":\"unnamed 42\",\"help\":\"try again\"}\n");
+}
+
+#[test]
+fn logfmt_output() {
+    if is_child() {
+        let source = "x".parse::<u8>().unwrap_err();
+        Terminate(Err(Test2::NamedA{value: "x", source})).report();
+        return;
+    }
+    assert_eq!(
+        child_stderr("logfmt_output", &[]),
+        "level=error variant=NamedA exit_code=4 message=\"failed to parse x\" code=E7 source=\"invalid digit found in string\"\n"
+    );
+    assert_eq!(
+        child_stderr("logfmt_output", &[("THISTERMINATION_FORMAT", "json")]),
+        "{\"variant\":\"NamedA\",\"exit_code\":4,\"message\":\"failed to parse x\",\"code\":\"E7\",\"source\":[\"invalid digit found in string\"]}\n"
+    );
+}
+
+#[test]
+fn env_format() {
+    if is_child() {
+        Terminate(Err(Test3::UnitA)).report();
+        return;
+    }
+    assert_eq!(child_stderr("env_format", &[]), "Error: unit a\n");
+    assert_eq!(child_stderr("env_format", &[("THISTERMINATION_FORMAT", "logfmt")]), "level=error variant=UnitA exit_code=5 message=\"unit a\"\n");
+    assert_eq!(child_stderr("env_format", &[("THISTERMINATION_FORMAT", "xml")]), "Error: unit a\n");
+}
+
+#[test]
+fn message_not_debug() {
+    if is_child() {
+        Terminate(Err(Test4::Status(404))).report();
+        return;
+    }
+    assert_eq!(format!("{:?}", Test4::Status(404)), "Status(404)");
+    assert_eq!(child_stderr("message_not_debug", &[]), "Error: status 404\n");
+    assert_eq!(child_stderr("message_not_debug", &[("THISTERMINATION_FORMAT", "json")]), "{\"variant\":\"Status\",\"exit_code\":6,\"message\":\"status 404\"}\n");
+}
+
+#[test]
+fn from_field_is_source() {
+    if is_child() {
+        Terminate(Err(Test5::from(io::Error::new(io::ErrorKind::NotFound, "no such file")))).report();
+        return;
+    }
+    assert_eq!(
+        child_stderr("from_field_is_source", &[]),
+        "{\"variant\":\"Read\",\"exit_code\":7,\"message\":\"cannot read config\",\"source\":[\"no such file\"]}\n"
+    );
+}
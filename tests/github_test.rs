@@ -0,0 +1,53 @@
+#![cfg(feature = "github")]
+
+use std::{process::Termination as _, num::ParseIntError};
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, Terminate};
+
+mod common;
+use common::{is_child, child_stdout_and_stderr};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("unit a: 100%"), help("try again,\nlater"))]
+    UnitA,
+}
+
+#[derive(Termination, Error)]
+#[termination(format = "json")]
+enum Test2 {
+    #[error("failed to parse {value}")]
+    NamedA{value: &'static str, #[source] source: ParseIntError},
+}
+
+#[test]
+fn github_output() {
+    if is_child() {
+        Terminate(Err(Test::UnitA)).report();
+        return;
+    }
+    let (stdout, stderr) = child_stdout_and_stderr("github_output", &[("GITHUB_ACTIONS", "true")]);
+    assert!(stdout.contains("::error title=UnitA::unit a: 100%25\n::notice title=UnitA::try again,%0Alater\n"), "{}", stdout);
+    assert_eq!(stderr, "");
+    let (stdout, stderr) = child_stdout_and_stderr("github_output", &[("GITHUB_ACTIONS", "true"), ("THISTERMINATION_FORMAT", "text")]);
+    assert!(!stdout.contains("::error"));
+    assert_eq!(stderr, "Error: unit a: 100%\nhelp: try again,\nlater\n");
+    let (stdout, _) = child_stdout_and_stderr("github_output", &[]);
+    assert!(!stdout.contains("::error"));
+}
+
+#[test]
+fn github_output_source() {
+    if is_child() {
+        let source = "x".parse::<u8>().unwrap_err();
+        Terminate(Err(Test2::NamedA{value: "x", source})).report();
+        return;
+    }
+    let (stdout, stderr) = child_stdout_and_stderr("github_output_source", &[("THISTERMINATION_FORMAT", "github")]);
+    assert!(stdout.contains("::error title=NamedA::failed to parse x%0Acaused by: invalid digit found in string\n"), "{}", stdout);
+    assert_eq!(stderr, "");
+    let (stdout, stderr) = child_stdout_and_stderr("github_output_source", &[("GITHUB_ACTIONS", "true")]);
+    assert!(!stdout.contains("::error"));
+    assert!(stderr.starts_with("{\"variant\":\"NamedA\""));
+}
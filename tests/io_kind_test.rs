@@ -0,0 +1,49 @@
+use std::{io::{self, ErrorKind}, process::{ExitCode, Termination as _}};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, exit_code_for_io_kind};
+
+#[derive(TerminationFull)]
+#[termination(exit_code(9))]
+enum Test {
+    #[termination(exit_code(from_io_kind), msg("io error: {0}"))]
+    Io(#[from] io::Error),
+    #[termination(exit_code(from_io_kind(NotFound = 2, PermissionDenied = 13)), msg("cannot open {path}: {source}"))]
+    Open{path: &'static str, source: io::Error},
+    #[termination(exit_code(from_io_kind), msg("not an io error"))]
+    NotIo(&'static str),
+}
+
+#[derive(Debug, Error)]
+#[error("cannot load config")]
+struct ConfigError(#[source] io::Error);
+
+#[derive(Termination, Error)]
+enum Test2 {
+    #[termination(exit_code(from_io_kind))]
+    #[error("config error")]
+    Config(#[source] ConfigError),
+}
+
+#[test]
+fn default_table() {
+    assert_eq!(Test::Io(io::Error::from(ErrorKind::NotFound)).report(), ExitCode::from(66));
+    assert_eq!(Test::Io(io::Error::from(ErrorKind::PermissionDenied)).report(), ExitCode::from(77));
+    assert_eq!(Test::Io(io::Error::from(ErrorKind::AddrInUse)).report(), ExitCode::from(69));
+    assert_eq!(Test::Io(io::Error::other("other")).report(), ExitCode::from(74));
+    assert_eq!(exit_code_for_io_kind(ErrorKind::TimedOut), 75);
+}
+
+#[test]
+fn overridden_table() {
+    assert_eq!(Test::Open{path: "a", source: io::Error::from(ErrorKind::NotFound)}.report(), ExitCode::from(2));
+    assert_eq!(Test::Open{path: "a", source: io::Error::from(ErrorKind::PermissionDenied)}.report(), ExitCode::from(13));
+    assert_eq!(Test::Open{path: "a", source: io::Error::from(ErrorKind::AlreadyExists)}.report(), ExitCode::from(73));
+}
+
+#[test]
+fn fallback_and_source_chain() {
+    assert_eq!(Test::NotIo("x").report(), ExitCode::from(9));
+    assert_eq!(Test2::Config(ConfigError(io::Error::from(ErrorKind::NotFound))).report(), ExitCode::from(66));
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "log")]
+
+use std::{num::ParseIntError, process::Termination as _, sync::Mutex};
+
+use log::{kv::{Key, Value, VisitSource}, Level, Log, Metadata, Record};
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, Terminate};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("request failed"), code("E0042"))]
+    RequestFailed,
+    #[termination(exit_code(0), msg("no changes"), silent)]
+    NoChanges,
+}
+
+#[derive(Termination, Error)]
+enum Test2 {
+    #[termination(exit_code(4))]
+    #[error("failed to parse {0}")]
+    ParseFailed(&'static str, #[source] ParseIntError),
+}
+
+fn flush_logs(_: &Test3) {
+    log::info!(target: "hook", "flushing logs");
+}
+
+#[derive(TerminationFull)]
+#[termination(on_exit = flush_logs)]
+enum Test3 {
+    #[termination(exit_code(5), msg("upload failed"))]
+    UploadFailed,
+}
+
+struct Logger;
+
+type RecordedLog = (Level, String, String, Vec<(String, String)>);
+
+static RECORDS: Mutex<Vec<RecordedLog>> = Mutex::new(Vec::new());
+
+struct Fields(Vec<(String, String)>);
+
+impl<'kvs> VisitSource<'kvs> for Fields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let mut fields = Fields(Vec::new());
+        record.key_values().visit(&mut fields).expect("visiting key values");
+        RECORDS.lock().expect("records lock").push((record.level(), record.target().to_string(), record.args().to_string(), fields.0));
+    }
+
+    fn flush(&self) {}
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
+
+// All reports are in one test, because the logger is global.
+#[test]
+fn log_records() {
+    log::set_logger(&Logger).expect("setting logger");
+    log::set_max_level(log::LevelFilter::Trace);
+    Terminate(Err(Test::RequestFailed)).report();
+    Terminate(Err(Test::NoChanges)).report();
+    Terminate(Err(Test2::ParseFailed("x", "x".parse::<u8>().unwrap_err()))).report();
+    // The event comes before the on_exit hooks, which may shut down the logger, and is also emitted without printing.
+    Test3::UploadFailed.report();
+    assert_eq!(*RECORDS.lock().expect("records lock"), [
+        (Level::Error, "thistermination".to_string(), "request failed".to_string(), fields(&[("variant", "RequestFailed"), ("exit_code", "3"), ("code", "E0042")])),
+        (Level::Error, "thistermination".to_string(), "failed to parse x".to_string(), fields(&[("variant", "ParseFailed"), ("exit_code", "4"), ("source", "invalid digit found in string")])),
+        (Level::Error, "thistermination".to_string(), "upload failed".to_string(), fields(&[("variant", "UploadFailed"), ("exit_code", "5")])),
+        (Level::Info, "hook".to_string(), "flushing logs".to_string(), Vec::new()),
+    ]);
+}
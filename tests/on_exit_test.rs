@@ -0,0 +1,67 @@
+use std::{process::{ExitCode, Termination as _}, sync::Mutex};
+
+use thistermination::TerminationFull;
+
+mod common;
+use common::{is_child, child_output};
+
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(call: String) {
+    CALLS.lock().expect("calls lock").push(call);
+}
+
+mod hooks {
+    pub fn remove_lock_file(error: &super::Test) {
+        super::record(format!("remove lock file: {:?}", error));
+    }
+}
+
+fn flush_telemetry(error: &Test) {
+    record(format!("flush telemetry: {:?}", error));
+}
+
+fn restore_terminal(_: &Test) {
+    record("restore terminal".to_string());
+}
+
+fn panicking_hook(_: &Test) {
+    panic!("hook failed");
+}
+
+#[derive(TerminationFull)]
+#[termination(on_exit = hooks::remove_lock_file, on_exit = flush_telemetry)]
+enum Test {
+    #[termination(exit_code(3), msg("unit a"), on_exit = restore_terminal)]
+    UnitA,
+    #[termination(exit_code(4), msg("unit b"))]
+    UnitB,
+    #[termination(exit_code(5), msg("unit c"), on_exit = panicking_hook)]
+    UnitC,
+}
+
+#[test]
+fn hooks_run_in_order() {
+    CALLS.lock().expect("calls lock").clear();
+    assert_eq!(Test::UnitA.report(), ExitCode::from(3));
+    assert_eq!(Test::UnitB.report(), ExitCode::from(4));
+    assert_eq!(*CALLS.lock().expect("calls lock"), [
+        "restore terminal",
+        "remove lock file: unit a",
+        "flush telemetry: unit a",
+        "remove lock file: unit b",
+        "flush telemetry: unit b",
+    ]);
+}
+
+#[test]
+fn panicking_hook_does_not_stop_termination() {
+    if is_child() {
+        Test::UnitC.exit();
+    }
+    let output = child_output("panicking_hook_does_not_stop_termination", &[]);
+    assert_eq!(output.status.code(), Some(5));
+    let stderr = String::from_utf8(output.stderr).expect("utf-8 stderr");
+    assert!(stderr.contains("hook failed"), "{}", stderr);
+    assert!(stderr.ends_with("Error: unit c\n"), "{}", stderr);
+}
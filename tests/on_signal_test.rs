@@ -0,0 +1,56 @@
+#![cfg(unix)]
+
+use std::{os::unix::process::ExitStatusExt, thread, time::Duration};
+
+use thistermination::TerminationFull;
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+#[termination(on_signal(SIGINT) = Interrupted, on_signal(SIGTERM, SIGHUP) = Terminated)]
+enum Test {
+    #[termination(msg("interrupted"), signal(SIGINT))]
+    Interrupted,
+    #[termination(exit_code(5), msg("terminated"))]
+    Terminated,
+}
+
+fn send_signal_to_self(signal: libc::c_int) {
+    // SAFETY: kill only sends `signal` to the current process.
+    assert_eq!(unsafe { libc::kill(libc::getpid(), signal) }, 0);
+    thread::sleep(Duration::from_secs(10));
+    panic!("the signal did not terminate the process");
+}
+
+#[test]
+fn sigint_terminates_through_variant() {
+    if is_child() {
+        Test::install_signal_handlers().expect("installing signal handlers");
+        send_signal_to_self(libc::SIGINT);
+    }
+    let output = child_output("sigint_terminates_through_variant", &[]);
+    assert_eq!(output.status.signal(), Some(2));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: interrupted\n");
+}
+
+#[test]
+fn sigterm_terminates_through_variant() {
+    if is_child() {
+        Test::install_signal_handlers().expect("installing signal handlers");
+        send_signal_to_self(libc::SIGTERM);
+    }
+    let output = child_output("sigterm_terminates_through_variant", &[]);
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: terminated\n");
+}
+
+#[test]
+fn install_only_once() {
+    if is_child() {
+        Test::install_signal_handlers().expect("installing signal handlers");
+        assert!(Test::install_signal_handlers().is_err());
+        return;
+    }
+    assert!(child_output("install_only_once", &[]).status.success());
+}
@@ -0,0 +1,105 @@
+use std::{process::Termination as _, num::ParseIntError};
+
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination, TerminationNoDebug, Terminate};
+
+mod common;
+use common::{is_child, child_stderr};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(msg("unit a"), help("try again"))]
+    UnitA,
+    #[termination(msg("a rather long message that does not fit into a terminal that is only thirty columns wide"))]
+    UnitB,
+}
+
+#[derive(Termination, Error)]
+enum Test3 {
+    #[termination(help("{value} is not a number"))]
+    #[error("failed to parse {value}")]
+    NamedA{value: &'static str, #[source] source: ParseIntError},
+}
+
+#[derive(TerminationNoDebug, Debug)]
+enum Test2 {
+    UnitA,
+}
+
+#[test]
+fn plain_output() {
+    if is_child() {
+        Terminate(Err(Test::UnitA)).report();
+        return;
+    }
+    assert_eq!(child_stderr("plain_output", &[]), "Error: unit a\nhelp: try again\n");
+}
+
+#[test]
+fn plain_output_with_fields() {
+    if is_child() {
+        let source = "x".parse::<u8>().unwrap_err();
+        Terminate(Err(Test3::NamedA{value: "x", source})).report();
+        return;
+    }
+    assert_eq!(child_stderr("plain_output_with_fields", &[]), "Error: failed to parse x\nhelp: x is not a number\n");
+}
+
+#[test]
+fn plain_output_not_wrapped() {
+    if is_child() {
+        Terminate(Err(Test::UnitB)).report();
+        return;
+    }
+    assert_eq!(
+        child_stderr("plain_output_not_wrapped", &[("COLUMNS", "30")]),
+        "Error: a rather long message that does not fit into a terminal that is only thirty columns wide\n"
+    );
+}
+
+#[test]
+fn report_prints_nothing() {
+    if is_child() {
+        Test::UnitA.report();
+        return;
+    }
+    assert_eq!(child_stderr("report_prints_nothing", &[]), "");
+}
+
+#[test]
+fn no_debug_output() {
+    if is_child() {
+        Terminate(Err(Test2::UnitA)).report();
+        return;
+    }
+    assert_eq!(child_stderr("no_debug_output", &[]), "");
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn colored_output() {
+    if is_child() {
+        let source = "x".parse::<u8>().unwrap_err();
+        Terminate(Err(Test3::NamedA{value: "x", source})).report();
+        return;
+    }
+    assert_eq!(
+        child_stderr("colored_output", &[("CLICOLOR_FORCE", "1")]),
+        "\x1b[1;31mError:\x1b[0m failed to parse x\n\x1b[1;33m  caused by:\x1b[0m invalid digit found in string\n\x1b[1;36mhelp:\x1b[0m x is not a number\n"
+    );
+    assert_eq!(child_stderr("colored_output", &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]), "Error: failed to parse x\nhelp: x is not a number\n");
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn wrapped_output() {
+    if is_child() {
+        Terminate(Err(Test::UnitB)).report();
+        return;
+    }
+    assert_eq!(
+        child_stderr("wrapped_output", &[("CLICOLOR_FORCE", "1"), ("COLUMNS", "30")]),
+        "\x1b[1;31mError:\x1b[0m a rather long message\n       that does not fit into\n       a terminal that is only\n       thirty columns wide\n"
+    );
+}
@@ -0,0 +1,92 @@
+use std::{env, thread};
+
+use thistermination::{TerminationFull, TerminationNoDebug};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+#[termination(panic = Internal)]
+enum Test {
+    #[termination(exit_code(70), msg("internal error: {message} at {location}"), help("please report this bug"))]
+    Internal{message: String, location: String},
+}
+
+#[derive(TerminationFull)]
+#[termination(panic = Bug)]
+enum Test2 {
+    #[termination(exit_code(71), msg("bug: {message}{}", backtrace.as_ref().map_or("", |_| " (with backtrace)")))]
+    Bug{message: String, backtrace: Option<String>},
+}
+
+#[derive(TerminationNoDebug)]
+#[termination(panic = Internal)]
+enum Test3 {
+    #[termination(exit_code(72))]
+    Internal,
+}
+
+fn failing_hook(_: &Test4) {
+    panic!("hook failed");
+}
+
+#[derive(TerminationFull)]
+#[termination(panic = Internal, on_exit = failing_hook)]
+enum Test4 {
+    #[termination(exit_code(70), msg("internal error"))]
+    Internal,
+    #[termination(exit_code(3), msg("failed"))]
+    Failed,
+}
+
+#[test]
+fn panic_terminates_through_variant() {
+    if is_child() {
+        Test::install_panic_hook();
+        let value: Option<u8> = env::var("THISTERMINATION_TEST_VALUE").ok().and_then(|value| value.parse().ok());
+        value.expect("value is missing");
+    }
+    let output = child_output("panic_terminates_through_variant", &[]);
+    assert_eq!(output.status.code(), Some(70));
+    let stderr = String::from_utf8(output.stderr).expect("utf-8 stderr");
+    assert!(stderr.starts_with("Error: internal error: value is missing at tests/panic_test.rs:"), "{}", stderr);
+    assert!(stderr.ends_with("\nhelp: please report this bug\n"), "{}", stderr);
+}
+
+#[test]
+fn panic_in_thread() {
+    if is_child() {
+        Test2::install_panic_hook();
+        thread::spawn(|| panic!("formatted {}", 42)).join().expect("the hook exits before join returns");
+    }
+    let output = child_output("panic_in_thread", &[]);
+    assert_eq!(output.status.code(), Some(71));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: bug: formatted 42\n");
+    let output = child_output("panic_in_thread", &[("RUST_BACKTRACE", "1")]);
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: bug: formatted 42 (with backtrace)\n");
+}
+
+#[test]
+fn panic_unit_variant() {
+    if is_child() {
+        Test3::install_panic_hook();
+        panic!("silent");
+    }
+    let output = child_output("panic_unit_variant", &[]);
+    assert_eq!(output.status.code(), Some(72));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn panic_in_exit_hook() {
+    if is_child() {
+        Test4::install_panic_hook();
+        Test4::Failed.exit();
+    }
+    let output = child_output("panic_in_exit_hook", &[]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("utf-8 stderr");
+    assert!(stderr.contains("panicked at tests/panic_test.rs:"), "{}", stderr);
+    assert!(stderr.contains("hook failed"), "{}", stderr);
+    assert!(stderr.ends_with("Error: failed\n"), "{}", stderr);
+}
@@ -0,0 +1,50 @@
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(msg("auth failed for {user} with {token} {token:?} [{token:>12}]"))]
+    AuthFailed{user: &'static str, #[termination(redact)] token: String},
+    #[termination(msg("unnamed {0} {1}"))]
+    UnnamedA(u8, #[termination(redact)] &'static str),
+}
+
+#[derive(TerminationFull)]
+#[termination(default_msg = "sentence")]
+enum Test2 {
+    InvalidToken(#[termination(redact)] String),
+}
+
+#[test]
+fn redacted_message() {
+    let named = Test::AuthFailed{user: "admin", token: "hunter2".to_string()};
+    assert_eq!(format!("{}", named), "auth failed for admin with [REDACTED] [REDACTED] [  [REDACTED]]");
+    assert_eq!(format!("{:?}", named), "auth failed for admin with [REDACTED] [REDACTED] [  [REDACTED]]");
+    let unnamed = Test::UnnamedA(42, "hunter2");
+    assert_eq!(format!("{}", unnamed), "unnamed 42 [REDACTED]");
+    let unnamed = Test2::InvalidToken("hunter2".to_string());
+    assert_eq!(format!("{}", unnamed), "invalid token: [REDACTED]");
+}
+
+#[test]
+fn redacted_debug() {
+    let named = Test::AuthFailed{user: "admin", token: "hunter2".to_string()};
+    assert_eq!(format!("{:#?}", named), "AuthFailed {\n    user: \"admin\",\n    token: [REDACTED],\n}");
+    let unnamed = Test::UnnamedA(42, "hunter2");
+    assert_eq!(format!("{:#?}", unnamed), "UnnamedA(\n    42,\n    [REDACTED],\n)");
+}
+
+#[derive(Termination, Error)]
+enum Test3 {
+    #[termination(msg("auth failed for {user} with {token}"))]
+    #[error("auth failed for {user}")]
+    AuthFailed{user: &'static str, #[termination(redact)] token: String},
+}
+
+#[test]
+fn redacted_thiserror() {
+    let named = Test3::AuthFailed{user: "admin", token: "hunter2".to_string()};
+    assert_eq!(format!("{:?}", named), "auth failed for admin with [REDACTED]");
+    assert_eq!(format!("{}", named), "auth failed for admin");
+}
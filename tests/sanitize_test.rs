@@ -0,0 +1,77 @@
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(msg("cannot open {path}"))]
+    OpenFailed{#[termination(sanitize)] path: String},
+    #[termination(msg("unnamed {0} {1:?} [{1:>16}]"))]
+    UnnamedA(String, #[termination(sanitize)] String),
+    #[termination(msg("unused"))]
+    Unused{#[termination(sanitize)] path: String, #[termination(redact, sanitize)] token: String},
+    #[termination(msg("cannot open {}", path.display()), help("{} bytes in {path:?}", path.as_os_str().len()))]
+    OpenPath{#[termination(sanitize)] path: std::path::PathBuf},
+    #[termination(msg("{name} is {}", name.len()))]
+    Named{#[termination(sanitize)] name: String},
+    #[termination(msg("{name}", name = "fixed"))]
+    NamedArgument{#[termination(sanitize)] name: String},
+    #[termination(msg("{} and {trimmed}", name, trimmed = name.trim()))]
+    PositionalArgument{#[termination(sanitize)] name: String},
+}
+
+#[derive(TerminationFull)]
+#[termination(sanitize)]
+enum Test2 {
+    #[termination(msg("{0} {1:.2} {2:>5}"))]
+    UnnamedA(String, f64, u8),
+    #[termination(msg("cannot open {}", path.display()), help("{}", path.display()))]
+    OpenPath{path: std::path::PathBuf},
+}
+
+#[derive(Termination, Error)]
+#[termination(sanitize)]
+enum Test3 {
+    #[termination(msg("cannot open {0}"))]
+    #[error("cannot open {0}")]
+    OpenFailed(String),
+}
+
+#[test]
+fn sanitized_field() {
+    let named = Test::OpenFailed{path: "\x1b]0;pwned\x07file\n.txt".to_string()};
+    assert_eq!(format!("{}", named), "cannot open \\u{1b}]0;pwned\\u{7}file\\n.txt");
+    assert_eq!(format!("{:?}", named), "cannot open \\u{1b}]0;pwned\\u{7}file\\n.txt");
+    let unnamed = Test::UnnamedA("\x1b[2J".to_string(), "\u{202e}txt.exe".to_string());
+    assert_eq!(format!("{}", unnamed), "unnamed \x1b[2J \"\\u{202e}txt.exe\" [ \\u{202e}txt.exe]");
+}
+
+#[test]
+fn sanitized_debug() {
+    let named = Test::Unused{path: "\x1b[31mred".to_string(), token: "hunter2".to_string()};
+    assert_eq!(format!("{}", named), "unused");
+    assert_eq!(format!("{:#?}", named), "Unused {\n    path: \"\\u{1b}[31mred\",\n    token: [REDACTED],\n}");
+}
+
+#[test]
+fn sanitized_enum() {
+    let unnamed = Test2::UnnamedA("\x1b[2Jclear\tall".to_string(), 1.0 / 3.0, 7);
+    assert_eq!(format!("{}", unnamed), "\\u{1b}[2Jclear\\tall 0.33     7");
+    let unnamed = Test3::OpenFailed("\x1b[2J".to_string());
+    assert_eq!(format!("{:?}", unnamed), "cannot open \\u{1b}[2J");
+    assert_eq!(format!("{}", unnamed), "cannot open \x1b[2J");
+}
+
+#[test]
+fn sanitized_field_in_arguments() {
+    let named = Test::OpenPath{path: "a\x1b[2Jb".into()};
+    assert_eq!(format!("{}", named), "cannot open a\\u{1b}[2Jb");
+    let named = Test::Named{name: "\x1b[2J".to_string()};
+    assert_eq!(format!("{}", named), "\\u{1b}[2J is 4");
+    let named = Test::NamedArgument{name: "\x1b[2J".to_string()};
+    assert_eq!(format!("{}", named), "fixed");
+    let named = Test::PositionalArgument{name: " \x1b[2J ".to_string()};
+    assert_eq!(format!("{}", named), " \\u{1b}[2J  and \\u{1b}[2J");
+    let named = Test2::OpenPath{path: "a\x1b[2Jb".into()};
+    assert_eq!(format!("{}", named), "cannot open a\\u{1b}[2Jb");
+}
@@ -0,0 +1,74 @@
+#![cfg(unix)]
+
+use std::{os::unix::process::ExitStatusExt, process::{ExitCode, Termination as _}};
+
+use thistermination::{TerminationFull, TerminationNoDebug, Terminate};
+
+mod common;
+use common::{is_child, child_output};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(msg("interrupted"), signal(SIGINT))]
+    Interrupted,
+    #[termination(msg("terminated by {0}"), signal(SIGTERM))]
+    Terminated(&'static str),
+    #[termination(exit_code(3), msg("failed"))]
+    Failed,
+}
+
+#[derive(TerminationNoDebug)]
+enum Test2 {
+    #[termination(signal(SIGHUP))]
+    HangUp,
+}
+
+#[test]
+fn reraises_sigint() {
+    if is_child() {
+        Terminate(Err(Test::Interrupted)).report();
+        return;
+    }
+    let output = child_output("reraises_sigint", &[]);
+    assert_eq!(output.status.signal(), Some(2));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: interrupted\n");
+}
+
+#[test]
+fn reraises_sigterm() {
+    if is_child() {
+        Terminate(Err(Test::Terminated("supervisor"))).report();
+        return;
+    }
+    let output = child_output("reraises_sigterm", &[]);
+    assert_eq!(output.status.signal(), Some(15));
+    assert_eq!(String::from_utf8(output.stderr).expect("utf-8 stderr"), "Error: terminated by supervisor\n");
+}
+
+#[test]
+fn flushes_stdout_before_raising() {
+    if is_child() {
+        print!("partial line");
+        Terminate(Err(Test::Interrupted)).report();
+        return;
+    }
+    let output = child_output("flushes_stdout_before_raising", &[]);
+    assert_eq!(output.status.signal(), Some(2));
+    assert!(String::from_utf8(output.stdout).expect("utf-8 stdout").ends_with("partial line"));
+}
+
+#[test]
+fn reraises_without_message() {
+    if is_child() {
+        Terminate(Err(Test2::HangUp)).report();
+        return;
+    }
+    let output = child_output("reraises_without_message", &[]);
+    assert_eq!(output.status.signal(), Some(1));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn other_variants_exit() {
+    assert_eq!(Test::Failed.report(), ExitCode::from(3));
+}
@@ -0,0 +1,35 @@
+#![cfg(unix)]
+
+use std::process::{Command, ExitCode, ExitStatus, Termination as _};
+
+use thistermination::TerminationFull;
+
+#[derive(TerminationFull)]
+#[termination(exit_code(7))]
+enum Test {
+    #[termination(exit_code(from_status = status), msg("{command} failed with {status}"))]
+    CommandFailed{command: &'static str, status: ExitStatus},
+    #[termination(exit_code(from_status = 1), msg("{0} failed with {1}"))]
+    Unnamed(&'static str, ExitStatus),
+}
+
+fn run(script: &str) -> ExitStatus {
+    Command::new("sh").args(["-c", script]).status().expect("running sh")
+}
+
+#[test]
+fn forwards_exit_code() {
+    assert_eq!(Test::CommandFailed{command: "sh", status: run("exit 3")}.report(), ExitCode::from(3));
+    assert_eq!(Test::Unnamed("sh", run("exit 42")).report(), ExitCode::from(42));
+}
+
+#[test]
+fn maps_signal() {
+    assert_eq!(Test::CommandFailed{command: "sh", status: run("kill -TERM $$")}.report(), ExitCode::from(128 + 15));
+    assert_eq!(Test::Unnamed("sh", run("kill -INT $$")).report(), ExitCode::from(128 + 2));
+}
+
+#[test]
+fn success_uses_fallback() {
+    assert_eq!(Test::CommandFailed{command: "sh", status: run("exit 0")}.report(), ExitCode::from(7));
+}
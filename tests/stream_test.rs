@@ -0,0 +1,70 @@
+use std::{env, process::Termination as _};
+
+use thistermination::{TerminationFull, Terminate};
+
+mod common;
+use common::{is_child, child_stdout_and_stderr};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(0), msg("no changes"), silent)]
+    NoChanges,
+    #[termination(exit_code(1), msg("files differ"), help("run with --diff"), stream = stdout)]
+    Differ,
+    #[termination(exit_code(2), msg("cannot read file"))]
+    ReadFailed,
+}
+
+#[derive(TerminationFull)]
+#[termination(stream = stdout)]
+enum Test2 {
+    #[termination(msg("usage: tool [options]"))]
+    Usage,
+    #[termination(msg("unexpected failure"), stream = stderr)]
+    Failure,
+}
+
+#[test]
+fn silent_variant() {
+    if is_child() {
+        let code = Terminate(Err(Test::NoChanges)).report();
+        assert_eq!(code, std::process::ExitCode::SUCCESS);
+        return;
+    }
+    assert_eq!(child_stdout_and_stderr("silent_variant", &[]), (String::new(), String::new()));
+    assert_eq!(child_stdout_and_stderr("silent_variant", &[("THISTERMINATION_FORMAT", "json")]), (String::new(), String::new()));
+}
+
+#[test]
+fn stdout_variant() {
+    if is_child() {
+        Terminate(Err(Test::Differ)).report();
+        Terminate(Err(Test::ReadFailed)).report();
+        return;
+    }
+    assert_eq!(child_stdout_and_stderr("stdout_variant", &[]), ("Error: files differ\nhelp: run with --diff\n".to_string(), "Error: cannot read file\n".to_string()));
+    assert_eq!(child_stdout_and_stderr("stdout_variant", &[("THISTERMINATION_FORMAT", "logfmt")]),
+        ("level=error variant=Differ exit_code=1 message=\"files differ\" help=\"run with --diff\"\n".to_string(), "level=error variant=ReadFailed exit_code=2 message=\"cannot read file\"\n".to_string()));
+}
+
+#[test]
+fn stdout_enum() {
+    if is_child() {
+        Terminate(Err(Test2::Usage)).report();
+        Terminate(Err(Test2::Failure)).report();
+        return;
+    }
+    assert_eq!(child_stdout_and_stderr("stdout_enum", &[]), ("Error: usage: tool [options]\n".to_string(), "Error: unexpected failure\n".to_string()));
+}
+
+#[test]
+fn exit_respects_silent_and_stream() {
+    if is_child() {
+        if env::var_os("THISTERMINATION_TEST_SILENT").is_some() {
+            Test::NoChanges.exit();
+        }
+        Test::Differ.exit();
+    }
+    assert_eq!(child_stdout_and_stderr("exit_respects_silent_and_stream", &[("THISTERMINATION_TEST_SILENT", "1")]), (String::new(), String::new()));
+    assert_eq!(child_stdout_and_stderr("exit_respects_silent_and_stream", &[]), ("Error: files differ\nhelp: run with --diff\n".to_string(), String::new()));
+}
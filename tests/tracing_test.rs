@@ -0,0 +1,73 @@
+#![cfg(feature = "tracing")]
+
+use std::{fmt::Debug, process::Termination as _, sync::Mutex};
+
+use tracing::{field::{Field, Visit}, span, Event, Level, Metadata, Subscriber};
+
+use thistermination::{TerminationFull, Terminate};
+
+#[derive(TerminationFull)]
+enum Test {
+    #[termination(exit_code(3), msg("request failed"), code("E0042"))]
+    RequestFailed,
+    #[termination(exit_code(5), msg("timeout"))]
+    Timeout,
+}
+
+type RecordedEvent = (Level, String, Vec<(String, String)>);
+
+static EVENTS: Mutex<Vec<RecordedEvent>> = Mutex::new(Vec::new());
+
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+struct Collector;
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(Vec::new());
+        event.record(&mut fields);
+        EVENTS.lock().expect("events lock").push((*event.metadata().level(), event.metadata().target().to_string(), fields.0));
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn tracing_events() {
+    tracing::subscriber::with_default(Collector, || {
+        Terminate(Err(Test::RequestFailed)).report();
+        Terminate(Err(Test::Timeout)).report();
+    });
+    assert_eq!(*EVENTS.lock().expect("events lock"), [
+        (Level::ERROR, "thistermination".to_string(), fields(&[("message", "request failed"), ("variant", "RequestFailed"), ("exit_code", "3"), ("code", "E0042")])),
+        (Level::ERROR, "thistermination".to_string(), fields(&[("message", "timeout"), ("variant", "Timeout"), ("exit_code", "5")])),
+    ]);
+}
@@ -0,0 +1,61 @@
+use std::process::{Termination, ExitCode};
+use thiserror::Error;
+
+use thistermination::{TerminationFull, Termination};
+
+#[derive(TerminationFull)]
+#[termination(wrap_msg("upload failed: {msg}"))]
+enum Test {
+    #[termination(exit_code(3), msg("unit a"))]
+    UnitA,
+    #[termination(msg("unnamed {0}"))]
+    UnnamedA(u8),
+    #[termination(msg("named {x}"))]
+    NamedA{x: u32},
+}
+
+#[derive(Termination, Error)]
+#[termination(wrap_msg("{variant} ({{{msg}}})"))]
+enum Test2 {
+    #[error("display {0}")]
+    UnnamedA(u8),
+    #[termination(msg("debug {x}"))]
+    #[error("display {x}")]
+    NamedA{x: u32},
+}
+
+#[derive(TerminationFull)]
+#[termination(wrap_msg("upload failed: {msg}"), default_msg = "sentence")]
+enum Test3 {
+    WrongAPIKey,
+}
+
+fn assert_eq_exit_code_and_int(ex: ExitCode, code: i32) {
+    assert_eq!(format!("{:?}", ex), format!("ExitCode(unix_exit_status({}))", code));
+}
+
+#[test]
+fn unit_wrap_msg() {
+    let unit = Test::UnitA;
+    assert_eq!(format!("{:?}", unit), "upload failed: unit a");
+    assert_eq!(format!("{}", unit), "upload failed: unit a");
+    assert_eq_exit_code_and_int(unit.report(), 3);
+    assert_eq!(format!("{}", Test3::WrongAPIKey), "upload failed: wrong api key");
+}
+
+#[test]
+fn unnamed_wrap_msg() {
+    let unnamed = Test::UnnamedA(42);
+    assert_eq!(format!("{}", unnamed), "upload failed: unnamed 42");
+    let unnamed = Test2::UnnamedA(42);
+    assert_eq!(format!("{:?}", unnamed), "UnnamedA ({display 42})");
+    assert_eq!(format!("{}", unnamed), "display 42");
+}
+
+#[test]
+fn named_wrap_msg() {
+    let named = Test::NamedA{x: 1337};
+    assert_eq!(format!("{}", named), "upload failed: named 1337");
+    let named = Test2::NamedA{x: 1337};
+    assert_eq!(format!("{:?}", named), "NamedA ({debug 1337})");
+}